@@ -1,6 +1,7 @@
 #!/bin/bash
 
-grep rw-p /proc/$1/maps \
+# Writable regions hold the game's state, executable ones its code for signature scans
+grep -E 'rw-p|r-xp' /proc/$1/maps \
 | sed -n 's/^\([0-9a-f]*\)-\([0-9a-f]*\) .*$/\1 \2/p' \
 | while read start stop; do \
     gdb --batch --pid $1 -ex \
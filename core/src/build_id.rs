@@ -0,0 +1,92 @@
+use std::{fmt::Display, path::Path};
+
+use crate::error::Error;
+
+const PT_NOTE: u32 = 4;
+const NT_GNU_BUILD_ID: u32 = 3;
+
+/// Identifies an exact build of the game executable.
+///
+/// Taken from the ELF `.note.gnu.build-id` when present, otherwise a hash of the whole file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BuildId(pub Vec<u8>);
+impl BuildId {
+    /// `path` can be `/proc/<pid>/exe`, which still works when the file on disk was replaced
+    pub fn for_executable(path: &Path) -> Result<Self, Error> {
+        let bytes = std::fs::read(path)?;
+        Ok(Self::from_elf(&bytes).unwrap_or_else(|| Self::from_contents(&bytes)))
+    }
+
+    pub fn from_elf(elf: &[u8]) -> Option<Self> {
+        fn u16_at(bytes: &[u8], offset: usize) -> Option<u16> {
+            Some(u16::from_le_bytes(
+                bytes.get(offset..offset + 2)?.try_into().ok()?,
+            ))
+        }
+        fn u32_at(bytes: &[u8], offset: usize) -> Option<u32> {
+            Some(u32::from_le_bytes(
+                bytes.get(offset..offset + 4)?.try_into().ok()?,
+            ))
+        }
+        fn u64_at(bytes: &[u8], offset: usize) -> Option<usize> {
+            let x = u64::from_le_bytes(bytes.get(offset..offset + 8)?.try_into().ok()?);
+            x.try_into().ok()
+        }
+
+        // 64 bit little endian only, same as the game
+        if elf.get(..6)? != b"\x7fELF\x02\x01" {
+            return None;
+        }
+
+        let ph_offset = u64_at(elf, 0x20)?;
+        let ph_size = u16_at(elf, 0x36)? as usize;
+        let ph_count = u16_at(elf, 0x38)? as usize;
+
+        (0..ph_count)
+            .map(|i| ph_offset + i * ph_size)
+            .filter(|&ph| u32_at(elf, ph) == Some(PT_NOTE))
+            .find_map(|ph| {
+                let start = u64_at(elf, ph + 0x8)?;
+                let size = u64_at(elf, ph + 0x20)?;
+                let notes = elf.get(start..start.checked_add(size)?)?;
+
+                Self::find_build_id_note(notes)
+            })
+    }
+
+    fn find_build_id_note(mut notes: &[u8]) -> Option<Self> {
+        let align = |x: usize| x.div_ceil(4) * 4;
+
+        while notes.len() >= 12 {
+            let name_size = u32::from_le_bytes(notes[0..4].try_into().ok()?) as usize;
+            let desc_size = u32::from_le_bytes(notes[4..8].try_into().ok()?) as usize;
+            let note_type = u32::from_le_bytes(notes[8..12].try_into().ok()?);
+
+            let name = notes.get(12..12 + name_size)?;
+            let desc_start = 12 + align(name_size);
+            let desc = notes.get(desc_start..desc_start + desc_size)?;
+
+            if note_type == NT_GNU_BUILD_ID && name == b"GNU\0" {
+                return Some(Self(desc.to_vec()));
+            }
+
+            notes = notes.get(desc_start + align(desc_size)..)?;
+        }
+
+        None
+    }
+
+    /// FNV-1a, so the id stays stable between runs and toolchains
+    pub fn from_contents(bytes: &[u8]) -> Self {
+        let hash = bytes.iter().fold(0xcbf29ce484222325u64, |hash, &b| {
+            (hash ^ b as u64).wrapping_mul(0x100000001b3)
+        });
+
+        Self(hash.to_be_bytes().to_vec())
+    }
+}
+impl Display for BuildId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.iter().try_for_each(|b| write!(f, "{b:02x}"))
+    }
+}
@@ -0,0 +1,111 @@
+use std::fmt::Display;
+
+use crate::{error::Error, pointer_path::PathValue, process::GameProcess, types::CGameEffect};
+
+/// Ticks of the game clock, which only advances while the game is unpaused
+pub const TICKS_PER_SECOND: u32 = 15;
+pub const SECONDS_PER_ROUND: u32 = 6;
+
+/// Point in game time, in ticks since the start of the game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GameTime(pub u32);
+impl GameTime {
+    /// Current time of the game, `None` if its layout has no `game_time` path
+    pub fn read(process: &GameProcess) -> Result<Option<Self>, Error> {
+        let Some(path) = &process.layout.clock.game_time else {
+            return Ok(None);
+        };
+
+        match path.eval(process, process.base_address.get())? {
+            PathValue::Unsigned(x) => Ok(Some(Self(x as u32))),
+            x => Err(Error::InvalidLayout {
+                name: "clock.game_time".into(),
+                msg: format!("{path} evaluates to {x} rather than an unsigned integer"),
+            }),
+        }
+    }
+
+    /// Time from `self` until `later`, `None` if it has passed
+    pub fn until(&self, later: GameTime) -> Option<GameDuration> {
+        later.0.checked_sub(self.0).map(GameDuration)
+    }
+}
+
+/// Span of game time in ticks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GameDuration(pub u32);
+impl GameDuration {
+    pub fn from_seconds(seconds: u32) -> Self {
+        Self(seconds * TICKS_PER_SECOND)
+    }
+
+    /// Rounded up, so an effect with a tick left still shows as lasting
+    pub fn seconds(&self) -> u32 {
+        self.0.div_ceil(TICKS_PER_SECOND)
+    }
+
+    /// Rounded up
+    pub fn rounds(&self) -> u32 {
+        self.0.div_ceil(TICKS_PER_SECOND * SECONDS_PER_ROUND)
+    }
+}
+impl Display for GameDuration {
+    /// e.g. `42s` or `3m 05s`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.seconds() {
+            x if x < 60 => write!(f, "{x}s"),
+            x => write!(f, "{}m {:02}s", x / 60, x % 60),
+        }
+    }
+}
+
+/// How much longer an effect lasts, see [`CGameEffect::remaining`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Remaining {
+    /// Applied once and kept until death or dispelled, e.g. a stat change from a potion
+    Permanent,
+    /// Lasts as long as the item which applied it is equipped
+    WhileEquipped,
+    Timed(GameDuration),
+    /// Takes effect after the delay, and then lasts as its duration type says
+    Delayed(GameDuration),
+    /// Ran out, and will be removed on the next update of the creature
+    Expired,
+    /// Duration type the engine doesn't document
+    Unknown(u32),
+}
+impl Display for Remaining {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Permanent => write!(f, "permanent"),
+            Self::WhileEquipped => write!(f, "while equipped"),
+            Self::Timed(x) => write!(f, "{x} left"),
+            Self::Delayed(x) => write!(f, "starts in {x}"),
+            Self::Expired => write!(f, "expired"),
+            Self::Unknown(x) => write!(f, "duration type {x}"),
+        }
+    }
+}
+
+impl CGameEffect {
+    /// Time left at `now`. Once applied, the engine rewrites `duration` of timed and delayed
+    /// effects into the game time they end or start at, which is what this reads it as
+    pub fn remaining(&self, now: GameTime) -> Remaining {
+        let at = |until: fn(GameDuration) -> Remaining| {
+            now.until(GameTime(self.duration))
+                .filter(|x| x.0 > 0)
+                .map_or(Remaining::Expired, until)
+        };
+
+        match self.duration_type {
+            // Limited in seconds, limited in ticks, and absolute
+            0 | 10 | 4096 => at(Remaining::Timed),
+            // Instant until death, and instant saved into the creature
+            1 | 9 => Remaining::Permanent,
+            2 => Remaining::WhileEquipped,
+            // Delayed limited, permanent and equipped, before and after the engine's update
+            3..=8 => at(Remaining::Delayed),
+            x => Remaining::Unknown(x),
+        }
+    }
+}
@@ -0,0 +1,299 @@
+use std::{collections::HashSet, ffi::c_void, marker::PhantomData};
+
+use crate::{
+    error::{Context, Error, ResultExt},
+    layout::{CPtrListLayout, CPtrListNodeLayout, CTypedPtrArrayLayout},
+    pod::Pod,
+    process::ProcessMemory,
+    remote_ptr::RemotePtr,
+    snapshot::{Snapshot, View},
+};
+
+/// Most elements a container iterator yields unless given another limit. Far more than the game
+/// ever holds, so only reached when following garbage
+pub const DEFAULT_LIMIT: usize = 0x10000;
+
+/// Elements read per syscall by [`ArrayIter`]
+const ARRAY_CHUNK: usize = 256;
+
+fn invalid(structure: &'static str, address: usize, msg: String) -> Error {
+    Error::InvalidContainer {
+        structure,
+        address,
+        msg,
+    }
+}
+
+/// MFC `CPtrList` of pointers to `T`. The count isn't trusted, see [`PtrListIter`]
+#[derive(Debug)]
+pub struct CPtrList<T = c_void> {
+    address: usize,
+    head: RemotePtr<c_void>,
+    count: u32,
+    _marker: PhantomData<T>,
+}
+impl<T> CPtrList<T> {
+    pub fn from_view(layout: &CPtrListLayout, view: View) -> Result<Self, Error> {
+        let view = view.named("CPtrList");
+
+        Ok(Self {
+            address: view.address(),
+            head: view.field("head", layout.head)?,
+            count: view.field("count", layout.count)?,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Number of nodes the list claims to have
+    pub fn len(&self) -> usize {
+        self.count as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Walks the nodes lazily, one read per node
+    pub fn iter<'a, P: ProcessMemory + Copy>(
+        &self,
+        process: P,
+        layout: &'a CPtrListNodeLayout,
+    ) -> PtrListIter<'a, P, T> {
+        PtrListIter {
+            process,
+            layout,
+            address: self.address,
+            next: self.head,
+            count: self.len(),
+            index: 0,
+            limit: DEFAULT_LIMIT,
+            visited: HashSet::new(),
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Data pointers of a [`CPtrList`].
+///
+/// Yields an error and stops when a node can't be read, the list loops back on itself, ends
+/// before its count, or is longer than the limit. Lists the game is modifying while we read
+/// them (e.g. effects during area transitions) end up here rather than in garbage.
+#[derive(Debug)]
+pub struct PtrListIter<'a, P, T> {
+    process: P,
+    layout: &'a CPtrListNodeLayout,
+    /// Of the list, for errors
+    address: usize,
+    next: RemotePtr<c_void>,
+    count: usize,
+    index: usize,
+    limit: usize,
+    visited: HashSet<usize>,
+    done: bool,
+    _marker: PhantomData<T>,
+}
+impl<P, T> PtrListIter<'_, P, T> {
+    pub fn with_limit(self, limit: usize) -> Self {
+        Self { limit, ..self }
+    }
+}
+impl<P: ProcessMemory + Copy, T> PtrListIter<'_, P, T> {
+    fn read_node(&mut self) -> Result<RemotePtr<T>, Error> {
+        let invalid = |msg| invalid("CPtrList", self.address, msg);
+
+        if self.index >= self.limit {
+            return Err(invalid(format!(
+                "has {} nodes, more than the limit of {}",
+                self.count, self.limit
+            )));
+        }
+
+        if self.next.is_null() {
+            return Err(invalid(format!(
+                "ends after {} of {} nodes",
+                self.index, self.count
+            )));
+        }
+
+        if !self.visited.insert(self.next.addr()) {
+            return Err(invalid(format!(
+                "node {} at {:#x} loops back to an earlier node",
+                self.index,
+                self.next.addr()
+            )));
+        }
+
+        let node = Snapshot::read(self.process, "CPtrListNode", self.next, self.layout.size)?;
+        let node = node.view();
+
+        let data: RemotePtr<c_void> = node.field("data", self.layout.data)?;
+        self.next = node.field("next", self.layout.next)?;
+        self.index += 1;
+
+        Ok(data.cast())
+    }
+}
+impl<P: ProcessMemory + Copy, T> Iterator for PtrListIter<'_, P, T> {
+    type Item = Result<RemotePtr<T>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.index >= self.count {
+            return None;
+        }
+
+        let node = self.read_node();
+        self.done = node.is_err();
+
+        Some(node)
+    }
+}
+
+/// MFC `CTypedPtrArray` of pointers to `T`
+#[derive(Debug)]
+pub struct CTypedPtrArray<T = c_void> {
+    elements: RemoteArray<RemotePtr<T>>,
+}
+impl<T> CTypedPtrArray<T> {
+    /// Fails if the size is negative, which only garbage has
+    pub fn from_view(layout: &CTypedPtrArrayLayout, view: View) -> Result<Self, Error> {
+        let view = view.named("CTypedPtrArray");
+
+        let data: RemotePtr<c_void> = view.field("data", layout.data)?;
+        let size: i32 = view.field("size", layout.size)?;
+        let size = usize::try_from(size).map_err(|_| {
+            invalid(
+                "CTypedPtrArray",
+                view.address(),
+                format!("has a negative size {size}"),
+            )
+        })?;
+
+        Ok(Self {
+            elements: RemoteArray::new("CTypedPtrArray", data.cast(), size),
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    pub fn iter<P: ProcessMemory + Copy>(&self, process: P) -> ArrayIter<P, RemotePtr<T>> {
+        self.elements.iter(process)
+    }
+}
+
+/// `len` consecutive `T` in remote memory, e.g. the storage of a `CTypedPtrArray` or a fixed
+/// size array member
+#[derive(Debug)]
+pub struct RemoteArray<T> {
+    /// Name of the structure, for errors
+    structure: &'static str,
+    ptr: RemotePtr<T>,
+    len: usize,
+}
+impl<T> RemoteArray<T> {
+    pub fn new(structure: &'static str, ptr: RemotePtr<T>, len: usize) -> Self {
+        Self {
+            structure,
+            ptr,
+            len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Reads the elements lazily, [`ARRAY_CHUNK`] at a time
+    pub fn iter<P: ProcessMemory + Copy>(&self, process: P) -> ArrayIter<P, T> {
+        ArrayIter {
+            process,
+            structure: self.structure,
+            ptr: self.ptr,
+            len: self.len,
+            index: 0,
+            limit: DEFAULT_LIMIT,
+            chunk: Vec::new().into_iter(),
+            done: false,
+        }
+    }
+}
+
+/// Elements of a [`RemoteArray`]. Yields an error and stops when a chunk can't be read or the
+/// array is longer than the limit
+#[derive(Debug)]
+pub struct ArrayIter<P, T> {
+    process: P,
+    structure: &'static str,
+    ptr: RemotePtr<T>,
+    len: usize,
+    /// Of the next element to read from the process
+    index: usize,
+    limit: usize,
+    chunk: std::vec::IntoIter<T>,
+    done: bool,
+}
+impl<P, T> ArrayIter<P, T> {
+    pub fn with_limit(self, limit: usize) -> Self {
+        Self { limit, ..self }
+    }
+}
+impl<P: ProcessMemory + Copy, T: Pod> ArrayIter<P, T> {
+    fn read_chunk(&mut self) -> Result<Vec<T>, Error> {
+        if self.index >= self.limit {
+            return Err(invalid(
+                self.structure,
+                self.ptr.addr(),
+                format!(
+                    "has {} elements, more than the limit of {}",
+                    self.len, self.limit
+                ),
+            ));
+        }
+
+        let count = ARRAY_CHUNK.min(self.len.min(self.limit) - self.index);
+        let ptr = self.ptr.byte_offset((self.index * size_of::<T>()) as isize);
+
+        let chunk = ptr.read_array(self.process, count).context(Context::Read {
+            structure: self.structure,
+            address: ptr.addr(),
+            size: count * size_of::<T>(),
+        })?;
+        self.index += count;
+
+        Ok(chunk)
+    }
+}
+impl<P: ProcessMemory + Copy, T: Pod> Iterator for ArrayIter<P, T> {
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(x) = self.chunk.next() {
+            return Some(Ok(x));
+        }
+
+        if self.done || self.index >= self.len {
+            return None;
+        }
+
+        match self.read_chunk() {
+            Ok(chunk) => {
+                self.chunk = chunk.into_iter();
+                self.chunk.next().map(Ok)
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use std::collections::BTreeSet;
+
+use crate::{
+    describe::{describe_spell_levels, school_name, secondary_type_name, weapon_type_name},
+    ids::{effect::Effect, school::School, secondary_type::SecondaryType, state::State},
+    types::{CDerivedStats, CGameEffect, CGameSprite, Lookup},
+};
+
+/// Spell levels a decrementing protection such as Spell Turning has left
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LevelPool {
+    pub levels: i32,
+    /// Highest spell level it covers
+    pub max_level: i32,
+}
+
+/// Resistances in percent, totalled over every effect by the engine
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Resistances {
+    pub fire: i16,
+    pub cold: i16,
+    pub electricity: i16,
+    pub acid: i16,
+    pub magic_fire: i16,
+    pub magic_cold: i16,
+    pub slashing: i16,
+    pub crushing: i16,
+    pub piercing: i16,
+    pub missile: i16,
+    /// Against magic damage, unlike [`Defences::magic_resistance`]
+    pub magic_damage: i32,
+    pub poison: i32,
+}
+impl Resistances {
+    pub fn from_stats(stats: &CDerivedStats) -> Self {
+        Self {
+            fire: stats.resist_fire,
+            cold: stats.resist_cold,
+            electricity: stats.resist_electricity,
+            acid: stats.resist_acid,
+            magic_fire: stats.resist_magic_fire,
+            magic_cold: stats.resist_magic_cold,
+            slashing: stats.resist_slashing,
+            crushing: stats.resist_crushing,
+            piercing: stats.resist_piercing,
+            missile: stats.resist_missile,
+            magic_damage: stats.magic_damage_resistance,
+            poison: stats.resist_poison,
+        }
+    }
+}
+
+/// Protections a creature has active, i.e. what has to be dispelled or breached before attacking
+/// it. See [`CGameSprite::defences`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Defences {
+    /// Spell levels it is immune to, e.g. 1 to 4 for Globe of Invulnerability
+    pub spell_levels: BTreeSet<i32>,
+    /// Spell Turning and the like
+    pub spell_turning: Vec<LevelPool>,
+    /// Spell Deflection and the like
+    pub spell_deflection: Vec<LevelPool>,
+    pub spell_trap: Vec<LevelPool>,
+    pub schools: BTreeSet<Lookup<School, u32>>,
+    /// Secondary types it is immune to, e.g. spell protections from Spell Shield
+    pub secondary_types: BTreeSet<Lookup<SecondaryType, u32>>,
+    /// Res refs of spells it is immune to
+    pub spells: BTreeSet<String>,
+    pub opcodes: BTreeSet<Lookup<Effect, u32>>,
+    pub projectiles: BTreeSet<i32>,
+
+    /// Enchantment a weapon needs to hit it, e.g. 3 for a +3 weapon
+    pub weapon_enchantment: Option<i32>,
+    /// Other kinds of weapons it is immune to, as `parameter2` of opcode 120
+    pub weapon_types: BTreeSet<i32>,
+    pub stoneskins: i32,
+    pub mirror_images: i32,
+
+    pub magic_resistance: i32,
+    pub resistances: Resistances,
+
+    pub invisible: bool,
+    pub improved_invisibility: bool,
+    pub non_detection: bool,
+    pub sanctuary: bool,
+    /// Sees through invisibility, e.g. from True Sight
+    pub true_sight: bool,
+}
+impl Defences {
+    /// Summarises `effects` on top of the totals in `stats`
+    pub fn new<'a>(
+        stats: &CDerivedStats,
+        effects: impl IntoIterator<Item = &'a CGameEffect>,
+    ) -> Self {
+        let state = stats.general_state;
+        let mut defences = Self {
+            stoneskins: stats.stone_skins,
+            magic_resistance: stats.resist_magic as i32,
+            resistances: Resistances::from_stats(stats),
+            invisible: state.is_invisible(),
+            improved_invisibility: state.contains(State::IMPROVED_INVISIBILITY),
+            non_detection: state.contains(State::NONDETECTION),
+            sanctuary: stats.sanctuary != 0,
+            true_sight: stats.see_invisible != 0,
+            ..Default::default()
+        };
+
+        for effect in effects {
+            defences.add(effect);
+        }
+
+        defences
+    }
+
+    fn add(&mut self, effect: &CGameEffect) {
+        let Lookup::Found(opcode) = effect.effect_id else {
+            return;
+        };
+        let p1 = effect.parameter1;
+        let p2 = effect.parameter2;
+        let pool = LevelPool {
+            levels: p1,
+            max_level: p2,
+        };
+
+        match opcode {
+            Effect::SpellImmunityByPowerLevel => {
+                self.spell_levels.insert(p1);
+            }
+            Effect::SpellBounceByPowerLevelDecrementing => self.spell_turning.push(pool),
+            Effect::SpellImmunityByPowerLevelDecrementing => self.spell_deflection.push(pool),
+            Effect::ProtectionSpellTrapByPowerLevelDecrementing => self.spell_trap.push(pool),
+            Effect::SpellProtectionBySchool | Effect::SpellImmunityBySchoolDecrementing => {
+                self.schools.insert(Lookup::decode(p2 as u32));
+            }
+            Effect::SpellProtectionBySecondaryType
+            | Effect::SpellImmunityBySecondaryTypeDecrementing => {
+                self.secondary_types.insert(Lookup::decode(p2 as u32));
+            }
+            Effect::SpellProtectionFromSpell | Effect::ProtectionFromResource => {
+                self.spells.insert(effect.res.clone());
+            }
+            Effect::ProtectionFromOpcode => {
+                self.opcodes.insert(Lookup::decode(p2 as u32));
+            }
+            Effect::ProtectionFromProjectile => {
+                self.projectiles.insert(p2);
+            }
+            Effect::ProtectionFromWeapons if p2 == 0 => {
+                self.weapon_enchantment = self.weapon_enchantment.max(Some(p1));
+            }
+            Effect::ProtectionFromWeapons => {
+                self.weapon_types.insert(p2);
+            }
+            // The stat counts down as skins are lost, the effect keeps the starting count
+            Effect::ProtectionStoneskin | Effect::SpellGolemStoneskin if self.stoneskins == 0 => {
+                self.stoneskins = p1;
+            }
+            Effect::SpellEffectMirrorImage | Effect::SpellEffectMirrorImageExactNumber => {
+                self.mirror_images = self.mirror_images.max(p1);
+            }
+            Effect::SpellEffectInvisibleDetectionByScript => self.true_sight = true,
+            _ => {}
+        }
+    }
+
+    /// One line per protection, e.g. `Immune to level 1-4 spells` or `Immune to weapons below +3`
+    pub fn summary(&self) -> Vec<String> {
+        let mut lines = describe_spell_levels(self.spell_levels.iter().copied());
+
+        let pools = [
+            ("Reflects", &self.spell_turning),
+            ("Absorbs", &self.spell_deflection),
+            ("Traps", &self.spell_trap),
+        ];
+        for (verb, pools) in pools {
+            for LevelPool { levels, max_level } in pools {
+                lines.push(format!(
+                    "{verb} {levels} spell levels of level {max_level} or lower"
+                ));
+            }
+        }
+
+        for school in &self.schools {
+            lines.push(format!("Immune to {} spells", school_name(school)));
+        }
+        for secondary_type in &self.secondary_types {
+            lines.push(format!(
+                "Immune to {} spells",
+                secondary_type_name(secondary_type)
+            ));
+        }
+        for spell in &self.spells {
+            lines.push(format!("Immune to {spell}"));
+        }
+        for opcode in &self.opcodes {
+            match opcode {
+                Lookup::Found(x) => lines.push(format!("Immune to {x:?}")),
+                Lookup::Unknown(x) => lines.push(format!("Immune to opcode {x}")),
+            }
+        }
+        for projectile in &self.projectiles {
+            lines.push(format!("Immune to projectile {projectile}"));
+        }
+
+        if let Some(enchantment) = self.weapon_enchantment {
+            lines.push(format!("Immune to weapons below +{enchantment}"));
+        }
+        for weapon_type in &self.weapon_types {
+            lines.push(format!("Immune to {}", weapon_type_name(*weapon_type)));
+        }
+        if self.stoneskins > 0 {
+            lines.push(format!("{} stoneskins", self.stoneskins));
+        }
+        if self.mirror_images > 0 {
+            lines.push(format!("{} mirror images", self.mirror_images));
+        }
+
+        if self.magic_resistance != 0 {
+            lines.push(format!("{}% magic resistance", self.magic_resistance));
+        }
+
+        let states = [
+            (self.improved_invisibility, "Improved invisibility"),
+            (self.invisible && !self.improved_invisibility, "Invisible"),
+            (self.non_detection, "Non-detection"),
+            (self.sanctuary, "Sanctuary"),
+            (self.true_sight, "True sight"),
+        ];
+        lines.extend(
+            states
+                .iter()
+                .filter(|(x, _)| *x)
+                .map(|(_, x)| x.to_string()),
+        );
+
+        lines
+    }
+}
+
+impl CGameSprite {
+    pub fn defences(&self) -> Defences {
+        Defences::new(
+            &self.derived_stats,
+            self.equipped_effects.iter().chain(&self.timed_effects),
+        )
+    }
+}
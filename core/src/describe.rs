@@ -0,0 +1,412 @@
+use crate::{
+    clock::{GameTime, Remaining},
+    ids::{effect::Effect, school::School, secondary_type::SecondaryType},
+    types::{CGameEffect, Lookup},
+};
+
+/// Stats which opcodes modify with `parameter1` as the amount and `parameter2` as the modifier
+/// type, with how the stat is written after the amount
+const STAT_MODIFIERS: &[(Effect, &str)] = &[
+    (Effect::StatAttacksPerRoundModifier, "attacks per round"),
+    (Effect::StatCharismaModifier, "Charisma"),
+    (Effect::StatConstitutionModifier, "Constitution"),
+    (Effect::StatDexterityModifier, "Dexterity"),
+    (Effect::MaximumHPModifier, "maximum HP"),
+    (Effect::StatIntelligenceModifier, "Intelligence"),
+    (Effect::StatLoreModifier, "Lore"),
+    (Effect::StatMoraleModifier, "morale"),
+    (Effect::StatAcidResistanceModifier, "% acid resistance"),
+    (Effect::StatColdResistanceModifier, "% cold resistance"),
+    (
+        Effect::StatElectricityResistanceModifier,
+        "% electricity resistance",
+    ),
+    (Effect::StatFireResistanceModifier, "% fire resistance"),
+    (
+        Effect::StatMagicDamageResistanceModifier,
+        "% magic damage resistance",
+    ),
+    (Effect::StatSaveVsDeathModifier, "save vs. death"),
+    (Effect::StatSaveVsWandsModifier, "save vs. wands"),
+    (
+        Effect::StatSaveVsPetrificationOrPolymorphModifier,
+        "save vs. polymorph",
+    ),
+    (Effect::StatSaveVsBreathWeaponsModifier, "save vs. breath"),
+    (Effect::StatSaveVsSpellsModifier, "save vs. spell"),
+    (Effect::StatStrengthModifier, "Strength"),
+    (Effect::StatWisdomModifier, "Wisdom"),
+    (Effect::StatTHAC0Modifier, "THAC0"),
+    (Effect::StatStealthModifier, "Move Silently"),
+    (Effect::StatExtraDamageModifier, "damage"),
+    (
+        Effect::StatMagicalFireResistanceModifier,
+        "% magical fire resistance",
+    ),
+    (
+        Effect::StatMagicalColdResistanceModifier,
+        "% magical cold resistance",
+    ),
+    (
+        Effect::StatSlashingResistanceModifier,
+        "% slashing resistance",
+    ),
+    (
+        Effect::StatCrushingResistanceModifier,
+        "% crushing resistance",
+    ),
+    (
+        Effect::StatPiercingResistanceModifier,
+        "% piercing resistance",
+    ),
+    (
+        Effect::StatMissilesResistanceModifier,
+        "% missile resistance",
+    ),
+    (Effect::StatOpenLocksModifier, "Open Locks"),
+    (Effect::StatFindTrapsModifier, "Find Traps"),
+    (Effect::StatPickPocketsModifier, "Pick Pockets"),
+    (Effect::StatTrackingSkillModifier, "Tracking"),
+    (
+        Effect::StatExceptionalStrengthModifier,
+        "exceptional Strength",
+    ),
+    (Effect::StatMovementModifier, "movement rate"),
+    (Effect::StatMagicResistanceModifier, "% magic resistance"),
+    (
+        Effect::StatTHAC0ModifierWithMissileWeapons,
+        "THAC0 with missile weapons",
+    ),
+    (Effect::StatPoisonResistanceModifier, "% poison resistance"),
+    (Effect::StatHideInShadowsModifier, "Hide in Shadows"),
+    (Effect::StatDetectIllusionModifier, "Detect Illusion"),
+    (Effect::StatSetTrapsModifier, "Set Traps"),
+    (Effect::StatToHitModifier, "to hit"),
+    (Effect::StatMeleeTHAC0Modifier, "melee THAC0"),
+    (Effect::StatMeleeWeaponDamageModifier, "melee damage"),
+    (Effect::StatMissileWeaponDamageModifier, "missile damage"),
+    (Effect::StatCriticalHitModifier, "critical hit chance"),
+];
+
+/// Opcodes which only set a state, whatever their parameters
+const STATES: &[(Effect, &str)] = &[
+    (Effect::StateBerserking, "Berserk"),
+    (Effect::Charm, "Charmed"),
+    (Effect::StateHaste, "Hasted"),
+    (Effect::StateHaste2, "Hasted"),
+    (Effect::StateInvisibility, "Invisible"),
+    (Effect::StateHorror, "Horrified"),
+    (Effect::StateSilence, "Silenced"),
+    (Effect::StateUnconsciousness, "Unconscious"),
+    (Effect::StateSlow, "Slowed"),
+    (Effect::StateStun, "Stunned"),
+    (Effect::StateInfravision, "Infravision"),
+    (Effect::ProtectionFromDetectionNonDetection, "Non-detection"),
+    (Effect::StateBlindness, "Blinded"),
+    (Effect::StateFeeblemindedness, "Feebleminded"),
+    (Effect::StateDisease, "Diseased"),
+    (Effect::StateDeafness, "Deafened"),
+    (Effect::StateParalyze, "Paralyzed"),
+    (Effect::StateConfusion, "Confused"),
+    (Effect::StateBless, "Blessed"),
+    (Effect::StatePositiveChant, "Chant"),
+    (Effect::StateNegativeChant, "Chanted against"),
+    (Effect::StatePetrification, "Petrified"),
+    (Effect::StateWebEffect, "Webbed"),
+    (Effect::StateHold, "Held"),
+    (Effect::StateHoldII, "Held"),
+    (Effect::ProtectionFreeAction, "Free action"),
+    (Effect::ProtectionFreedom, "Immune to imprisonment and maze"),
+    (
+        Effect::SpellEffectInvisibleDetectionByScript,
+        "Sees invisible creatures",
+    ),
+    (Effect::ProtectionBackstab, "Immune to backstab"),
+    (Effect::ProtectionFromTimestop, "Immune to time stop"),
+    (
+        Effect::SpellEffectImmunityToTurnUndead,
+        "Immune to turn undead",
+    ),
+];
+
+/// Names of the bits of the damage type in the upper half of `parameter2` of opcode 12
+const DAMAGE_TYPES: &[(u32, &str)] = &[
+    (0x10000, "acid"),
+    (0x20000, "cold"),
+    (0x40000, "electricity"),
+    (0x80000, "fire"),
+    (0x100000, "piercing"),
+    (0x200000, "poison"),
+    (0x400000, "magic"),
+    (0x800000, "missile"),
+    (0x1000000, "slashing"),
+    (0x2000000, "magic fire"),
+    (0x4000000, "magic cold"),
+    (0x8000000, "non-lethal"),
+];
+
+/// Weapons opcode 120 protects from, by `parameter2`
+const WEAPON_TYPES: &[&str] = &[
+    "enchanted",
+    "magical",
+    "non-magical",
+    "silver",
+    "non-silver",
+    "non-silver non-magical",
+    "two-handed",
+    "one-handed",
+    "cursed",
+    "non-cursed",
+    "cold iron",
+    "non-cold iron",
+];
+
+fn find<T: Copy>(table: &[(Effect, T)], effect: Effect) -> Option<T> {
+    table
+        .iter()
+        .find(|(x, _)| *x == effect)
+        .map(|(_, value)| *value)
+}
+
+/// `amount` of `stat` as applied with the modifier type `kind`, e.g. `+2 Strength`. Stats
+/// starting with `% ` are percentages
+fn modifier(amount: i32, kind: i32, stat: &str) -> String {
+    let (unit, stat) = match stat.strip_prefix("% ") {
+        Some(stat) => ("%", stat),
+        None => ("", stat),
+    };
+
+    match kind {
+        1 => format!("{stat} set to {amount}{unit}"),
+        2 => format!("{stat} at {amount}% of normal"),
+        _ => format!("{amount:+}{unit} {stat}"),
+    }
+}
+
+/// e.g. `Necromancy`, or `school 12` for one SCHOOL.IDS doesn't name
+pub(crate) fn school_name(school: &Lookup<School, u32>) -> String {
+    match school {
+        Lookup::Found(x) => format!("{x:?}"),
+        Lookup::Unknown(x) => format!("school {x}"),
+    }
+}
+
+pub(crate) fn secondary_type_name(secondary_type: &Lookup<SecondaryType, u32>) -> String {
+    match secondary_type {
+        Lookup::Found(x) => format!("{x:?}"),
+        Lookup::Unknown(x) => format!("secondary type {x}"),
+    }
+}
+
+/// e.g. `silver weapons`, for `parameter2` of opcode 120 besides 0, which is by enchantment
+pub(crate) fn weapon_type_name(value: i32) -> String {
+    match WEAPON_TYPES.get(value as usize) {
+        Some(kind) => format!("{kind} weapons"),
+        None => format!("weapon type {value}"),
+    }
+}
+
+fn opcode_name(value: i32) -> String {
+    match Lookup::<Effect, u32>::decode(value as u32) {
+        Lookup::Found(x) => format!("{x:?}"),
+        Lookup::Unknown(x) => format!("opcode {x}"),
+    }
+}
+
+fn dice(effect: &CGameEffect) -> String {
+    let amount = effect.parameter1;
+
+    match (effect.dice_thrown, effect.dice_sides) {
+        (0, _) | (_, 0) => amount.to_string(),
+        (thrown, sides) if amount == 0 => format!("{thrown}d{sides}"),
+        (thrown, sides) => format!("{thrown}d{sides}{amount:+}"),
+    }
+}
+
+fn damage(effect: &CGameEffect) -> String {
+    let damage_type = effect.parameter2 as u32 & 0xFFFF0000;
+    let name = DAMAGE_TYPES
+        .iter()
+        .find(|(bit, _)| *bit == damage_type)
+        .map(|(_, name)| *name)
+        .unwrap_or("crushing");
+
+    format!("{} {name} damage", dice(effect))
+}
+
+fn armor_class(effect: &CGameEffect) -> String {
+    const TYPES: &[(i32, &str)] = &[
+        (0x1, "crushing"),
+        (0x2, "missile"),
+        (0x4, "piercing"),
+        (0x8, "slashing"),
+    ];
+
+    if effect.parameter2 == 0x10 {
+        return format!("Base AC set to {}", effect.parameter1);
+    }
+
+    let types = TYPES
+        .iter()
+        .filter(|(bit, _)| effect.parameter2 & bit != 0)
+        .map(|(_, name)| *name)
+        .collect::<Vec<_>>();
+
+    match types.as_slice() {
+        [] => format!("{:+} AC", effect.parameter1),
+        types => format!("{:+} AC vs. {}", effect.parameter1, types.join(", ")),
+    }
+}
+
+impl CGameEffect {
+    /// What the effect does, e.g. `+2 AC vs. slashing` or `Immune to level 4 spells`. Opcodes
+    /// without a description of their own are written by name
+    pub fn describe(&self) -> String {
+        let effect = match self.effect_id {
+            Lookup::Found(x) => x,
+            Lookup::Unknown(x) => return format!("Opcode {x}"),
+        };
+
+        if let Some(stat) = find(STAT_MODIFIERS, effect) {
+            return modifier(self.parameter1, self.parameter2, stat);
+        }
+
+        if let Some(state) = find(STATES, effect) {
+            return state.into();
+        }
+
+        let p1 = self.parameter1;
+        let p2 = self.parameter2;
+
+        match effect {
+            Effect::StatACVsDamageTypeModifier => armor_class(self),
+            Effect::HPDamage => damage(self),
+            Effect::CurrentHPModifier => format!("Heals {} HP", dice(self)),
+            Effect::HPRegeneration => format!("Regenerates {p1} HP"),
+            Effect::StatCumulativeLuckBonus => format!("{p1:+} Luck"),
+            Effect::StatePoison => "Poisoned".into(),
+            Effect::ProtectionFromOpcode => format!("Immune to {}", opcode_name(p2)),
+            Effect::ProtectionFromProjectile => format!("Immune to projectile {p2}"),
+            Effect::SpellImmunityByPowerLevel => format!("Immune to level {p1} spells"),
+            Effect::SpellBounceByPowerLevel => format!("Reflects level {p1} spells"),
+            Effect::SpellImmunityByPowerLevelDecrementing => {
+                format!("Absorbs {p1} spell levels of level {p2} or lower")
+            }
+            Effect::SpellBounceByPowerLevelDecrementing => {
+                format!("Reflects {p1} spell levels of level {p2} or lower")
+            }
+            Effect::ProtectionSpellTrapByPowerLevelDecrementing => {
+                format!("Traps {p1} spell levels of level {p2} or lower")
+            }
+            Effect::SpellProtectionBySchool => {
+                format!(
+                    "Immune to {} spells",
+                    school_name(&Lookup::decode(p2 as u32))
+                )
+            }
+            Effect::SpellImmunityBySchoolDecrementing => format!(
+                "Absorbs {p1} {} spells",
+                school_name(&Lookup::decode(p2 as u32))
+            ),
+            Effect::SpellProtectionBySecondaryType => {
+                format!(
+                    "Immune to {} spells",
+                    secondary_type_name(&Lookup::decode(p2 as u32))
+                )
+            }
+            Effect::SpellImmunityBySecondaryTypeDecrementing => {
+                format!(
+                    "Absorbs {p1} {} spells",
+                    secondary_type_name(&Lookup::decode(p2 as u32))
+                )
+            }
+            Effect::SpellProtectionFromSpell | Effect::ProtectionFromResource => {
+                format!("Immune to {}", self.res)
+            }
+            Effect::ProtectionFromWeapons => match p2 {
+                0 => format!("Immune to weapons below +{p1}"),
+                _ => format!("Immune to {}", weapon_type_name(p2)),
+            },
+            Effect::ProtectionStoneskin | Effect::SpellGolemStoneskin => {
+                format!("{p1} stoneskins")
+            }
+            Effect::SpellEffectMirrorImage | Effect::SpellEffectMirrorImageExactNumber => {
+                format!("{p1} mirror images")
+            }
+            Effect::RemovalRemoveSchool | Effect::RemovalRemoveOneSchool => {
+                format!("Removes {} spells", school_name(&Lookup::decode(p2 as u32)))
+            }
+            Effect::RemovalRemoveSecondaryType | Effect::RemovalRemoveOneSecondaryType => {
+                format!(
+                    "Removes {} spells of level {p1} or lower",
+                    secondary_type_name(&Lookup::decode(p2 as u32))
+                )
+            }
+            x => format!("{x:?}"),
+        }
+    }
+}
+
+/// Spell levels an opcode 102 protection covers, merged into ranges like
+/// `Immune to level 1-4 spells`, as spells such as Globe of Invulnerability apply one per level
+pub fn describe_spell_levels(levels: impl IntoIterator<Item = i32>) -> Vec<String> {
+    let mut levels = levels.into_iter().collect::<Vec<_>>();
+    levels.sort();
+    levels.dedup();
+
+    let mut ranges: Vec<(i32, i32)> = Vec::new();
+    for level in levels {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == level => *end = level,
+            _ => ranges.push((level, level)),
+        }
+    }
+
+    ranges
+        .into_iter()
+        .map(|(start, end)| match start == end {
+            true => format!("Immune to level {start} spells"),
+            false => format!("Immune to level {start}-{end} spells"),
+        })
+        .collect()
+}
+
+/// Descriptions of `effects`, with opcode 102 protections merged by [`describe_spell_levels`].
+/// Given `now`, each is followed by how much longer it lasts, e.g. `Hasted: 42s left`, and only
+/// levels which last as long are merged
+pub fn describe_effects<'a>(
+    effects: impl IntoIterator<Item = &'a CGameEffect>,
+    now: Option<GameTime>,
+) -> Vec<String> {
+    let with_remaining = |description: String, remaining: Option<Remaining>| match remaining {
+        Some(x) => format!("{description}: {x}"),
+        None => description,
+    };
+
+    let mut levels: Vec<(Option<Remaining>, Vec<i32>)> = Vec::new();
+    let mut descriptions = Vec::new();
+
+    for effect in effects {
+        let remaining = now.map(|now| effect.remaining(now));
+
+        match effect.effect_id {
+            Lookup::Found(Effect::SpellImmunityByPowerLevel) => {
+                match levels.iter_mut().find(|(x, _)| *x == remaining) {
+                    Some((_, x)) => x.push(effect.parameter1),
+                    None => levels.push((remaining, vec![effect.parameter1])),
+                }
+            }
+            _ => descriptions.push(with_remaining(effect.describe(), remaining)),
+        }
+    }
+
+    levels
+        .into_iter()
+        .flat_map(|(remaining, levels)| {
+            describe_spell_levels(levels)
+                .into_iter()
+                .map(move |x| with_remaining(x, remaining))
+        })
+        .chain(descriptions)
+        .collect()
+}
@@ -1,5 +1,7 @@
 use std::fmt::Display;
 
+use crate::{build_id::BuildId, permissions::PermissionDiagnostics, title::GameTitle};
+
 #[derive(Debug)]
 pub enum Error {
     Io(std::io::Error),
@@ -15,16 +17,234 @@ pub enum Error {
         msg: String,
         bytes: Vec<u8>,
     },
-    InvalidEnumValue { enum_type: &'static str, value: String },
+    InvalidEnumValue {
+        enum_type: &'static str,
+        value: String,
+    },
+    InvalidMemoryMap {
+        line: usize,
+        msg: String,
+    },
+    InvalidSignature {
+        signature: String,
+        msg: String,
+    },
+    InvalidPointerPath {
+        path: String,
+        msg: String,
+    },
+    InvalidObjectSpec {
+        spec: String,
+        msg: String,
+    },
+    SignatureNotFound {
+        name: &'static str,
+    },
+    InvalidLayout {
+        name: String,
+        msg: String,
+    },
+    MissingLayout {
+        title: GameTitle,
+        build_id: BuildId,
+    },
+    SnapshotOutOfBounds {
+        offset: isize,
+        length: usize,
+        size: usize,
+    },
+    /// Value doesn't fit in the width of the field it was written to
+    ValueOutOfRange {
+        field: &'static str,
+        value: i64,
+        min: i64,
+        max: i64,
+    },
+    /// A remote list or array is inconsistent, e.g. loops back on itself
+    InvalidContainer {
+        structure: &'static str,
+        address: usize,
+        msg: String,
+    },
+    /// Every memory backend was refused, see [`PermissionDiagnostics`] for why
+    PermissionDenied(Box<PermissionDiagnostics>),
+    /// `source` happened while doing what `context` describes
+    Context {
+        context: Context,
+        source: Box<Error>,
+    },
+}
+impl Error {
+    pub fn context(self, context: Context) -> Self {
+        Self::Context {
+            context,
+            source: Box::new(self),
+        }
+    }
+
+    /// Innermost error, without any [`Context`]
+    pub fn root(&self) -> &Error {
+        match self {
+            Self::Context { source, .. } => source.root(),
+            x => x,
+        }
+    }
+
+    /// This error followed by its sources, e.g. `entity 12: CDerivedStats.max_hp at 0x1004: ...`
+    pub fn report(&self) -> String {
+        let mut report = self.to_string();
+
+        let mut source = std::error::Error::source(self);
+        while let Some(x) = source {
+            report.push_str(&format!(": {x}"));
+            source = x.source();
+        }
+
+        report
+    }
+
+    /// Context of this error, outermost first
+    pub fn contexts(&self) -> impl Iterator<Item = &Context> {
+        let mut error = self;
+
+        std::iter::from_fn(move || match error {
+            Self::Context { context, source } => {
+                error = source;
+                Some(context)
+            }
+            _ => None,
+        })
+    }
 }
 impl Display for Error {
+    /// Only this error, not its [`source`](std::error::Error::source), see [`Error::report`]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{self:?}")
+        match self {
+            Self::Io(_) => write!(f, "I/O error"),
+            Self::Memory(msg) => write!(f, "{msg}"),
+            Self::InsufficentMemory {
+                msg,
+                expected,
+                actual,
+            } => write!(f, "{msg}: needs {expected} bytes, buffer holds {actual}"),
+            Self::MissingGameProcess => write!(f, "No running game process found"),
+            Self::GameProcessClosed => write!(f, "The game process has closed"),
+            Self::InvalidString { msg, bytes } => write!(f, "Invalid string {bytes:02x?}: {msg}"),
+            Self::InvalidEnumValue { enum_type, value } => {
+                write!(f, "{value} is not a valid {enum_type}")
+            }
+            Self::InvalidMemoryMap { line, msg } => {
+                write!(f, "Invalid memory map at line {line}: {msg}")
+            }
+            Self::InvalidSignature { signature, msg } => {
+                write!(f, "Invalid signature `{signature}`: {msg}")
+            }
+            Self::InvalidPointerPath { path, msg } => {
+                write!(f, "Invalid pointer path `{path}`: {msg}")
+            }
+            Self::InvalidObjectSpec { spec, msg } => {
+                write!(f, "Invalid object specifier `{spec}`: {msg}")
+            }
+            Self::SignatureNotFound { name } => {
+                write!(f, "Signature for {name} not found in the executable")
+            }
+            Self::InvalidLayout { name, msg } => write!(f, "Invalid layout {name}: {msg}"),
+            Self::MissingLayout { title, build_id } => {
+                write!(f, "No layout for {title} with build id {build_id}")
+            }
+            Self::SnapshotOutOfBounds {
+                offset,
+                length,
+                size,
+            } => write!(
+                f,
+                "{length} bytes at offset {offset:#x} are outside of the {size:#x} byte snapshot"
+            ),
+            Self::ValueOutOfRange {
+                field,
+                value,
+                min,
+                max,
+            } => write!(
+                f,
+                "{value} does not fit in {field}, which holds {min} to {max}"
+            ),
+            Self::InvalidContainer {
+                structure,
+                address,
+                msg,
+            } => write!(f, "{structure} at {address:#x} {msg}"),
+            Self::PermissionDenied(diagnostics) => write!(f, "{diagnostics}"),
+            Self::Context { context, .. } => write!(f, "{context}"),
+        }
+    }
+}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Context { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
     }
 }
-impl std::error::Error for Error {}
 impl From<std::io::Error> for Error {
     fn from(value: std::io::Error) -> Self {
         Self::Io(value)
     }
 }
+
+/// What was being read when an [`Error`] happened
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Context {
+    /// Reading `size` bytes of `structure` at `address`
+    Read {
+        structure: &'static str,
+        address: usize,
+        size: usize,
+    },
+    /// Reading `count` instances of `structure` in one batch
+    ReadMany {
+        structure: &'static str,
+        count: usize,
+    },
+    /// Decoding `field` of `structure`, which is at `address`
+    Field {
+        structure: &'static str,
+        field: &'static str,
+        address: usize,
+    },
+    /// Reading the entity with this id from the entity list
+    Entity { id: u16 },
+    /// Evaluating the `index`th step of a pointer path
+    PathStep { index: usize, step: String },
+}
+impl Display for Context {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Read {
+                structure,
+                address,
+                size,
+            } => write!(f, "reading {structure} ({size:#x} bytes) at {address:#x}"),
+            Self::ReadMany { structure, count } => write!(f, "reading {count} {structure}"),
+            Self::Field {
+                structure,
+                field,
+                address,
+            } => write!(f, "{structure}.{field} at {address:#x}"),
+            Self::Entity { id } => write!(f, "entity {id}"),
+            Self::PathStep { index, step } => write!(f, "step {index} `{step}`"),
+        }
+    }
+}
+
+pub trait ResultExt<T> {
+    /// Adds `context` to the error, if any
+    fn context(self, context: Context) -> Result<T, Error>;
+}
+impl<T, E: Into<Error>> ResultExt<T> for Result<T, E> {
+    fn context(self, context: Context) -> Result<T, Error> {
+        self.map_err(|e| e.into().context(context))
+    }
+}
@@ -1,4 +1,6 @@
-crate::int_enum! {
+use crate::{error::Error, int_enum};
+
+int_enum! {
     pub enum Alignment: u8 {
         None = 0x00,
         LawfulGood = 0x11,
@@ -10,11 +12,115 @@ crate::int_enum! {
         ChaoticGood = 0x31,
         ChaoticNeutral = 0x32,
         ChaoticEvil = 0x33,
-        MaskGood = 0x01,
-        MaskGENeutral = 0x02,
-        MaskEvil = 0x03,
-        MaskLawful = 0x10,
-        MaskLCNeutral = 0x20,
-        MaskChaotic = 0x30,
+    }
+}
+
+// High nibble of an alignment
+int_enum! {
+    pub enum LawChaos: u8 {
+        Lawful = 0x10,
+        Neutral = 0x20,
+        Chaotic = 0x30,
+    }
+}
+
+// Low nibble of an alignment
+int_enum! {
+    pub enum GoodEvil: u8 {
+        Good = 0x01,
+        Neutral = 0x02,
+        Evil = 0x03,
+    }
+}
+
+impl Alignment {
+    pub fn new(law_chaos: LawChaos, good_evil: GoodEvil) -> Self {
+        Self::try_from(law_chaos as u8 | good_evil as u8)
+            .expect("every pair of axes is an alignment")
+    }
+
+    /// `None` for [`Alignment::None`]
+    pub fn law_chaos(self) -> Option<LawChaos> {
+        LawChaos::try_from(self as u8 & 0xF0).ok()
+    }
+
+    /// `None` for [`Alignment::None`]
+    pub fn good_evil(self) -> Option<GoodEvil> {
+        GoodEvil::try_from(self as u8 & 0x0F).ok()
+    }
+}
+
+/// Alignment as scripts match it, where an axis left out matches either way, e.g. `MASK_EVIL`
+/// matches lawful, neutral and chaotic evil
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AlignmentMask {
+    pub law_chaos: Option<LawChaos>,
+    pub good_evil: Option<GoodEvil>,
+}
+impl AlignmentMask {
+    /// Names ALIGN.IDS gives the masks
+    pub const NAMES: &[(&str, AlignmentMask)] = &[
+        ("MASK_GOOD", Self::either_law_chaos(GoodEvil::Good)),
+        ("MASK_GENEUTRAL", Self::either_law_chaos(GoodEvil::Neutral)),
+        ("MASK_EVIL", Self::either_law_chaos(GoodEvil::Evil)),
+        ("MASK_LAWFUL", Self::either_good_evil(LawChaos::Lawful)),
+        ("MASK_LCNEUTRAL", Self::either_good_evil(LawChaos::Neutral)),
+        ("MASK_CHAOTIC", Self::either_good_evil(LawChaos::Chaotic)),
+    ];
+
+    pub const fn either_law_chaos(good_evil: GoodEvil) -> Self {
+        Self {
+            law_chaos: None,
+            good_evil: Some(good_evil),
+        }
+    }
+
+    pub const fn either_good_evil(law_chaos: LawChaos) -> Self {
+        Self {
+            law_chaos: Some(law_chaos),
+            good_evil: None,
+        }
+    }
+
+    pub fn matches(&self, alignment: Alignment) -> bool {
+        self.includes(alignment as u8)
+    }
+
+    /// Whether the raw `alignment` is one of `self`
+    pub fn includes(self, alignment: u8) -> bool {
+        self.law_chaos.is_none_or(|x| x as u8 == alignment & 0xF0)
+            && self.good_evil.is_none_or(|x| x as u8 == alignment & 0x0F)
+    }
+}
+impl From<Alignment> for AlignmentMask {
+    fn from(value: Alignment) -> Self {
+        Self {
+            law_chaos: value.law_chaos(),
+            good_evil: value.good_evil(),
+        }
+    }
+}
+impl From<AlignmentMask> for u8 {
+    fn from(value: AlignmentMask) -> u8 {
+        value.law_chaos.map_or(0, |x| x as u8) | value.good_evil.map_or(0, |x| x as u8)
+    }
+}
+impl TryFrom<u8> for AlignmentMask {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        let law_chaos = match value & 0xF0 {
+            0 => None,
+            x => Some(LawChaos::try_from(x)?),
+        };
+        let good_evil = match value & 0x0F {
+            0 => None,
+            x => Some(GoodEvil::try_from(x)?),
+        };
+
+        Ok(Self {
+            law_chaos,
+            good_evil,
+        })
     }
 }
@@ -210,6 +210,59 @@ impl Class {
         self.class_count() > 1
     }
 
+    /// Whether `class` is one of `self` in a script, where e.g. `MAGE_ALL` stands for every class
+    /// with mage levels
+    pub fn includes(self, class: u8) -> bool {
+        let Ok(class) = Class::try_from(class) else {
+            return class == self as u8;
+        };
+
+        match self {
+            Class::MageAll => matches!(
+                class,
+                Class::Mage
+                    | Class::FighterMage
+                    | Class::MageThief
+                    | Class::ClericMage
+                    | Class::FighterMageThief
+                    | Class::FighterMageCleric
+                    | Class::Sorcerer
+            ),
+            Class::FighterAll => matches!(
+                class,
+                Class::Fighter
+                    | Class::FighterMage
+                    | Class::FighterCleric
+                    | Class::FighterThief
+                    | Class::FighterMageThief
+                    | Class::FighterDruid
+                    | Class::FighterMageCleric
+            ),
+            Class::ClericAll => matches!(
+                class,
+                Class::Cleric
+                    | Class::FighterCleric
+                    | Class::ClericMage
+                    | Class::ClericThief
+                    | Class::FighterMageCleric
+                    | Class::ClericRanger
+            ),
+            Class::ThiefAll => matches!(
+                class,
+                Class::Thief
+                    | Class::FighterThief
+                    | Class::MageThief
+                    | Class::ClericThief
+                    | Class::FighterMageThief
+            ),
+            Class::BardAll => class == Class::Bard,
+            Class::PaladinAll => class == Class::Paladin,
+            Class::DruidAll => matches!(class, Class::Druid | Class::FighterDruid),
+            Class::RangerAll => matches!(class, Class::Ranger | Class::ClericRanger),
+            x => class == x,
+        }
+    }
+
     pub fn get_levels(
         &self,
         CDerivedStats {
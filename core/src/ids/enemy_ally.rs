@@ -26,3 +26,54 @@ int_enum! {
         Enemy = 255, // Creatures that are hostile to the party and allied creatures.
     }
 }
+
+impl EnemyAlly {
+    pub fn hostility(self) -> Hostility {
+        Hostility::of(self as u8)
+    }
+
+    /// Whether an object with allegiance `ea` is one of `self` in a script, where the cutoffs
+    /// and `NOT*` values stand for a range of allegiances
+    pub fn includes(self, ea: u8) -> bool {
+        match self {
+            Self::Anyone | Self::Anything => true,
+            Self::Goodcutoff => ea <= self as u8,
+            Self::Notgood => ea >= self as u8,
+            Self::NotNeutral => ea <= Self::Goodcutoff as u8 || ea >= Self::EvilCutoff as u8,
+            Self::NotEvil => ea <= self as u8,
+            Self::EvilCutoff => ea >= self as u8,
+            x => ea == x as u8,
+        }
+    }
+}
+
+/// How an allegiance stands towards the party, going by the colour of its selection circle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Hostility {
+    /// Green circle
+    Friendly,
+    /// Blue circle
+    Neutral,
+    /// Red circle
+    Hostile,
+    /// No circle, e.g. statues and area objects
+    Inanimate,
+}
+impl Hostility {
+    /// Hostility of the raw allegiance `ea`, which also places values outside of EA.IDS by the
+    /// range they fall in
+    pub fn of(ea: u8) -> Self {
+        use EnemyAlly as Ea;
+
+        match ea {
+            x if x == Ea::Inanimate as u8 || x == Ea::AreaObject as u8 => Self::Inanimate,
+            x if x == Ea::GoodButRed as u8 => Self::Hostile,
+            x if x == Ea::GoodButBlue as u8 => Self::Neutral,
+            x if x == Ea::EvilButGreen as u8 => Self::Friendly,
+            x if x == Ea::EvilButBlue as u8 => Self::Neutral,
+            x if x <= Ea::Goodcutoff as u8 => Self::Friendly,
+            x if x >= Ea::EvilCutoff as u8 => Self::Hostile,
+            _ => Self::Neutral,
+        }
+    }
+}
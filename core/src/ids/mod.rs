@@ -1,10 +1,55 @@
 pub mod alignment;
 pub mod classes;
+pub mod effect;
 pub mod enemy_ally;
 pub mod gender;
 pub mod general;
+pub mod proficiency;
 pub mod race;
-pub mod effect;
+pub mod school;
+pub mod secondary_type;
+pub mod state;
+
+use crate::types::Lookup;
+
+/// IDS files that differ between titles
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdsFile {
+    Ea,
+    General,
+    Race,
+    Class,
+    Gender,
+    Align,
+}
+
+/// IDS tables a title decodes creatures with. The enums in this module follow the Baldur's Gate
+/// files, so values which mean something else in another title are left as [`Lookup::Unknown`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdsTables {
+    BaldursGate,
+    IcewindDale,
+    Torment,
+}
+impl IdsTables {
+    /// Whether `value` in `file` means the same in these tables as in Baldur's Gate
+    pub fn shares(&self, file: IdsFile, value: u8) -> bool {
+        match (self, file) {
+            (Self::BaldursGate, _) => true,
+            // Player races and classes match, the monster entries from 100 on are its own
+            (Self::IcewindDale, IdsFile::Race | IdsFile::Class) => value < 100,
+            (Self::Torment, IdsFile::Race | IdsFile::Class) => false,
+            _ => true,
+        }
+    }
+
+    pub fn lookup<T: TryFrom<u8>>(&self, file: IdsFile, value: u8) -> Lookup<T, u8> {
+        match T::try_from(value) {
+            Ok(x) if self.shares(file, value) => Lookup::Found(x),
+            _ => Lookup::Unknown(value),
+        }
+    }
+}
 
 #[macro_export]
 macro_rules! int_enum {
@@ -28,5 +73,15 @@ macro_rules! int_enum {
                 }
             }
         }
+
+        impl From<$name> for $repr {
+            fn from(value: $name) -> $repr {
+                value as $repr
+            }
+        }
+
+        impl $name {
+            pub const VARIANTS: &'static [$name] = &[$($name::$k),+];
+        }
     };
 }
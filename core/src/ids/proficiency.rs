@@ -0,0 +1,35 @@
+// Weapon proficiencies, see WEAPPROF.IDS. The values are also their stats in STATS.IDS
+crate::int_enum! {
+    pub enum Proficiency: u8 {
+        BastardSword = 89,
+        LongSword = 90,
+        ShortSword = 91,
+        Axe = 92,
+        TwoHandedSword = 93,
+        Katana = 94,
+        ScimitarWakizashiNinjato = 95,
+        Dagger = 96,
+        WarHammer = 97,
+        Spear = 98,
+        Halberd = 99,
+        FlailMorningStar = 100,
+        Mace = 101,
+        QuarterStaff = 102,
+        Crossbow = 103,
+        LongBow = 104,
+        ShortBow = 105,
+        Dart = 106,
+        Sling = 107,
+        Blackjack = 108,
+        Gun = 109,
+        MartialArts = 110,
+        TwoHandedWeaponSkill = 111,
+        SwordAndShieldSkill = 112,
+        SingleWeaponSkill = 113,
+        TwoWeaponSkill = 114,
+    }
+}
+impl Proficiency {
+    pub const FIRST: Self = Self::BastardSword;
+    pub const COUNT: usize = 26;
+}
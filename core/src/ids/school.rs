@@ -0,0 +1,14 @@
+// Spell schools, see SCHOOL.IDS
+crate::int_enum! {
+    pub enum School: u32 {
+        Abjuration = 1,
+        Conjuration = 2,
+        Divination = 3,
+        Enchantment = 4,
+        Illusion = 5,
+        Evocation = 6,
+        Necromancy = 7,
+        Alteration = 8,
+        Generalist = 9,
+    }
+}
@@ -0,0 +1,18 @@
+// Secondary types of spells, which dispels such as breach remove by, see MSECTYPE.IDS
+crate::int_enum! {
+    pub enum SecondaryType: u32 {
+        SpellProtections = 1,
+        SpecificProtections = 2,
+        IllusionaryProtections = 3,
+        MagicAttack = 4,
+        DivinationAttack = 5,
+        Conjuration = 6,
+        CombatProtections = 7,
+        Contingency = 8,
+        Battleground = 9,
+        OffensiveDamage = 10,
+        Disabling = 11,
+        Combination = 12,
+        NonCombat = 13,
+    }
+}
@@ -0,0 +1,80 @@
+use bitflags::bitflags;
+
+use crate::pod::Pod;
+
+bitflags! {
+    /// General state of a creature, see STATE.IDS
+    #[repr(transparent)]
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+    pub struct State: u32 {
+        const SLEEPING = 0x1;
+        const BERSERK = 0x2;
+        const PANIC = 0x4;
+        const STUNNED = 0x8;
+        const INVISIBLE = 0x10;
+        const HELPLESS = 0x20;
+        const FROZEN_DEATH = 0x40;
+        /// Petrified
+        const STONE_DEATH = 0x80;
+        const EXPLODING_DEATH = 0x100;
+        const FLAME_DEATH = 0x200;
+        const ACID_DEATH = 0x400;
+        const DEAD = 0x800;
+        const SILENCED = 0x1000;
+        const CHARMED = 0x2000;
+        const POISONED = 0x4000;
+        const HASTED = 0x8000;
+        const SLOWED = 0x10000;
+        const INFRAVISION = 0x20000;
+        const BLIND = 0x40000;
+        const DISEASED = 0x80000;
+        const FEEBLEMINDED = 0x100000;
+        const NONDETECTION = 0x200000;
+        const IMPROVED_INVISIBILITY = 0x400000;
+        const BLESS = 0x800000;
+        const CHANT = 0x1000000;
+        const DRAW_UPON_HOLY_MIGHT = 0x2000000;
+        const LUCK = 0x4000000;
+        const AID = 0x8000000;
+        const CHANT_BAD = 0x10000000;
+        const BLUR = 0x20000000;
+        const MIRROR_IMAGE = 0x40000000;
+        const CONFUSED = 0x80000000;
+
+        /// `STATE_REALLY_DEAD`, any way of dying
+        const REALLY_DEAD = Self::FROZEN_DEATH.bits()
+            | Self::STONE_DEATH.bits()
+            | Self::EXPLODING_DEATH.bits()
+            | Self::FLAME_DEATH.bits()
+            | Self::ACID_DEATH.bits()
+            | Self::DEAD.bits();
+        /// `STATE_DISABLED`, states in which a creature can't act on its own
+        const DISABLED = Self::SLEEPING.bits()
+            | Self::BERSERK.bits()
+            | Self::PANIC.bits()
+            | Self::STUNNED.bits()
+            | Self::HELPLESS.bits()
+            | Self::REALLY_DEAD.bits()
+            | Self::CHARMED.bits()
+            | Self::FEEBLEMINDED.bits()
+            | Self::CONFUSED.bits();
+    }
+}
+// Any bit pattern is a valid `State`, unknown bits are kept
+unsafe impl Pod for State {}
+assert_eq_size!(State, u32);
+
+impl State {
+    pub fn is_dead(&self) -> bool {
+        self.intersects(Self::REALLY_DEAD)
+    }
+
+    /// Like the engine, includes being dead
+    pub fn is_disabled(&self) -> bool {
+        self.intersects(Self::DISABLED)
+    }
+
+    pub fn is_invisible(&self) -> bool {
+        self.intersects(Self::INVISIBLE | Self::IMPROVED_INVISIBILITY)
+    }
+}
@@ -0,0 +1,130 @@
+use std::{borrow::Cow, fmt::Display, path::Path, str::FromStr};
+
+use encoding_rs::{
+    EUC_KR, Encoding, GBK, SHIFT_JIS, UTF_8, WINDOWS_1250, WINDOWS_1251, WINDOWS_1252, WINDOWS_1254,
+};
+
+use crate::error::Error;
+
+/// Language the game's text is in, which decides how strings in its memory are decoded
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Language {
+    #[default]
+    English,
+    French,
+    German,
+    Italian,
+    Spanish,
+    Portuguese,
+    Polish,
+    Czech,
+    Hungarian,
+    Russian,
+    Ukrainian,
+    Turkish,
+    Japanese,
+    Korean,
+    Chinese,
+}
+impl Language {
+    /// Directory names under `lang/` in an Enhanced Edition install
+    const LOCALES: &[(&str, Language)] = &[
+        ("en_US", Self::English),
+        ("fr_FR", Self::French),
+        ("de_DE", Self::German),
+        ("it_IT", Self::Italian),
+        ("es_ES", Self::Spanish),
+        ("pt_BR", Self::Portuguese),
+        ("pl_PL", Self::Polish),
+        ("cs_CZ", Self::Czech),
+        ("hu_HU", Self::Hungarian),
+        ("ru_RU", Self::Russian),
+        ("uk_UA", Self::Ukrainian),
+        ("tr_TR", Self::Turkish),
+        ("ja_JP", Self::Japanese),
+        ("ko_KR", Self::Korean),
+        ("zh_CN", Self::Chinese),
+    ];
+
+    pub fn from_locale(locale: &str) -> Option<Self> {
+        Self::LOCALES
+            .iter()
+            .find(|(x, _)| x.eq_ignore_ascii_case(locale))
+            .map(|(_, language)| *language)
+    }
+
+    pub fn locale(&self) -> &'static str {
+        Self::LOCALES
+            .iter()
+            .find(|(_, x)| x == self)
+            .map(|(locale, _)| *locale)
+            .unwrap()
+    }
+
+    /// Language of the game at `path` in procfs, going by the `lang/<locale>/dialog.tlk` it has
+    /// open. `None` if it has none open or its file descriptors can't be read
+    pub fn detect(path: &Path) -> Option<Self> {
+        std::fs::read_dir(path.join("fd"))
+            .ok()?
+            .filter_map(|x| std::fs::read_link(x.ok()?.path()).ok())
+            .find_map(|target| {
+                let mut components = target.iter().rev().map(|x| x.to_str());
+
+                match (components.next()?, components.next()?, components.next()?) {
+                    (Some(file), Some(locale), Some(dir))
+                        if file.eq_ignore_ascii_case("dialog.tlk")
+                            && dir.eq_ignore_ascii_case("lang") =>
+                    {
+                        Self::from_locale(locale)
+                    }
+                    _ => None,
+                }
+            })
+    }
+
+    /// Code page the original games and older mods wrote this language's text in
+    pub fn legacy_encoding(&self) -> &'static Encoding {
+        match self {
+            Self::English
+            | Self::French
+            | Self::German
+            | Self::Italian
+            | Self::Spanish
+            | Self::Portuguese => WINDOWS_1252,
+            Self::Polish | Self::Czech | Self::Hungarian => WINDOWS_1250,
+            Self::Russian | Self::Ukrainian => WINDOWS_1251,
+            Self::Turkish => WINDOWS_1254,
+            Self::Japanese => SHIFT_JIS,
+            Self::Korean => EUC_KR,
+            Self::Chinese => GBK,
+        }
+    }
+
+    /// Decodes `bytes` up to the first NUL. Enhanced Edition text is UTF-8, anything which isn't
+    /// (e.g. names from mods converted from the original games) is read as
+    /// [`Self::legacy_encoding`]. Never fails, bytes which are invalid in both are replaced
+    pub fn decode<'a>(&self, bytes: &'a [u8]) -> Cow<'a, str> {
+        let end = bytes.iter().position(|x| *x == 0).unwrap_or(bytes.len());
+        let bytes = &bytes[..end];
+
+        UTF_8
+            .decode_without_bom_handling_and_without_replacement(bytes)
+            .unwrap_or_else(|| self.legacy_encoding().decode_without_bom_handling(bytes).0)
+    }
+}
+impl Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.locale())
+    }
+}
+impl FromStr for Language {
+    type Err = Error;
+
+    /// Case insensitive locale, e.g. `pl_PL`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_locale(s).ok_or_else(|| Error::InvalidEnumValue {
+            enum_type: "Language",
+            value: s.into(),
+        })
+    }
+}
@@ -0,0 +1,224 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::LazyLock,
+};
+
+use serde::Deserialize;
+
+use crate::{
+    build_id::BuildId,
+    error::Error,
+    pointer_path::PointerPath,
+    title::GameTitle,
+    types::{
+        CAIObjectTypeLayout, CCreatureFileHeaderLayout, CDerivedStatsLayout, CGameAIBaseLayout,
+        CGameEffectLayout, CGameSpriteLayout,
+    },
+};
+
+const BUILTIN_LAYOUTS: &[(&str, &str)] = &[("bgee.toml", include_str!("../layouts/bgee.toml"))];
+
+/// Overrides the user layout directory
+pub const LAYOUT_DIR_ENV: &str = "BG_RADAR_LAYOUT_DIR";
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GameInfo {
+    pub titles: Vec<GameTitle>,
+    #[serde(default)]
+    pub build_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CPtrListLayout {
+    pub head: isize,
+    pub count: isize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CPtrListNodeLayout {
+    /// Bytes read per snapshot, which must cover every field below
+    pub size: usize,
+    pub next: isize,
+    pub data: isize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CTypedPtrArrayLayout {
+    pub data: isize,
+    pub size: isize,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClockLayout {
+    /// Path to `CInfGame::m_worldTime.m_gameTime`, ending in `u32`. Unset in layouts it hasn't
+    /// been found for yet, which leaves effect durations unknown
+    pub game_time: Option<PointerPath>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GlobalsLayout {
+    /// Offset of the entity list from the executable's load address, for when its signature
+    /// doesn't match, see [`crate::signature::Globals::scan`]
+    pub entity_list: Option<usize>,
+}
+
+/// Structure offsets for a set of game builds, loaded from the TOML files in `core/layouts` and
+/// the user's layout directory
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Layout {
+    pub game: GameInfo,
+    #[serde(default)]
+    pub clock: ClockLayout,
+    #[serde(default)]
+    pub globals: GlobalsLayout,
+    #[serde(rename = "CGameAIBase", default)]
+    pub ai_base: CGameAIBaseLayout,
+    #[serde(rename = "CAIObjectType", default)]
+    pub ai_object_type: CAIObjectTypeLayout,
+    #[serde(rename = "CGameSprite", default)]
+    pub sprite: CGameSpriteLayout,
+    #[serde(rename = "CCreatureFileHeader", default)]
+    pub creature_file_header: CCreatureFileHeaderLayout,
+    #[serde(rename = "CDerivedStats", default)]
+    pub derived_stats: CDerivedStatsLayout,
+    #[serde(rename = "CGameEffect", default)]
+    pub effect: CGameEffectLayout,
+    #[serde(rename = "CPtrList")]
+    pub ptr_list: CPtrListLayout,
+    #[serde(rename = "CPtrListNode")]
+    pub ptr_list_node: CPtrListNodeLayout,
+    #[serde(rename = "CTypedPtrArray")]
+    pub typed_ptr_array: CTypedPtrArrayLayout,
+}
+impl Layout {
+    pub fn parse(name: &str, toml: &str) -> Result<Self, Error> {
+        toml::from_str(toml).map_err(|e| Error::InvalidLayout {
+            name: name.into(),
+            msg: e.to_string(),
+        })
+    }
+
+    pub fn applies_to(&self, title: GameTitle) -> bool {
+        self.game.titles.contains(&title)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LayoutDatabase {
+    /// In priority order, user layouts before the built in ones
+    layouts: Vec<Layout>,
+}
+impl LayoutDatabase {
+    pub fn builtin() -> Result<Self, Error> {
+        let layouts = BUILTIN_LAYOUTS
+            .iter()
+            .map(|(name, toml)| Layout::parse(name, toml))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { layouts })
+    }
+
+    /// Built in layouts, plus any from [`user_layout_dir`]
+    pub fn load() -> Result<Self, Error> {
+        let mut db = Self::builtin()?;
+
+        if let Some(dir) = user_layout_dir()
+            && dir.is_dir()
+        {
+            db.load_dir(&dir)?;
+        }
+
+        Ok(db)
+    }
+
+    /// [`load`](Self::load)ed on first use and kept for the rest of the run, so attaching again
+    /// across restarts neither re-reads the user layouts nor reports their errors anew
+    pub fn shared() -> Result<&'static Self, Error> {
+        static SHARED: LazyLock<Result<LayoutDatabase, Error>> =
+            LazyLock::new(LayoutDatabase::load);
+
+        match &*SHARED {
+            Ok(db) => Ok(db),
+            Err(Error::InvalidLayout { name, msg }) => Err(Error::InvalidLayout {
+                name: name.clone(),
+                msg: msg.clone(),
+            }),
+            Err(e) => Err(Error::InvalidLayout {
+                name: "layouts".into(),
+                msg: e.to_string(),
+            }),
+        }
+    }
+
+    /// Adds every `*.toml` file in `dir`, taking priority over layouts already loaded
+    pub fn load_dir(&mut self, dir: &Path) -> Result<(), Error> {
+        let io_error = |path: &Path, e: std::io::Error| Error::InvalidLayout {
+            name: path.display().to_string(),
+            msg: e.to_string(),
+        };
+
+        let mut paths = std::fs::read_dir(dir)
+            .and_then(|x| {
+                x.map(|entry| entry.map(|x| x.path()))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .map_err(|e| io_error(dir, e))?;
+        paths.retain(|x| x.extension().is_some_and(|ext| ext == "toml"));
+        paths.sort();
+
+        let layouts = paths
+            .iter()
+            .map(|path| {
+                let toml = std::fs::read_to_string(path).map_err(|e| io_error(path, e))?;
+                Layout::parse(&path.display().to_string(), &toml)
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        self.layouts.splice(0..0, layouts);
+
+        Ok(())
+    }
+
+    pub fn insert(&mut self, layout: Layout) {
+        self.layouts.insert(0, layout);
+    }
+
+    pub fn layouts(&self) -> &[Layout] {
+        &self.layouts
+    }
+
+    /// Picks the layout verified against `build_id`, or failing that the generic layout for
+    /// `title`
+    pub fn select(&self, title: GameTitle, build_id: &BuildId) -> Result<&Layout, Error> {
+        let build_id_str = build_id.to_string();
+        let for_title = || self.layouts.iter().filter(|x| x.applies_to(title));
+
+        for_title()
+            .find(|x| x.game.build_ids.contains(&build_id_str))
+            .or_else(|| for_title().find(|x| x.game.build_ids.is_empty()))
+            .ok_or_else(|| Error::MissingLayout {
+                title,
+                build_id: build_id.clone(),
+            })
+    }
+}
+
+/// `$BG_RADAR_LAYOUT_DIR`, or `$XDG_CONFIG_HOME/bg-radar-linux/layouts`
+pub fn user_layout_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os(LAYOUT_DIR_ENV) {
+        return Some(dir.into());
+    }
+
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+
+    Some(config_dir.join("bg-radar-linux").join("layouts"))
+}
@@ -4,25 +4,50 @@
 #[macro_use]
 extern crate static_assertions;
 
+pub mod build_id;
+pub mod clock;
+pub mod containers;
+pub mod defences;
+pub mod describe;
 pub mod error;
+pub mod ids;
+pub mod language;
+pub mod layout;
+pub mod memory_map;
+pub mod object_spec;
 pub mod padding;
+pub mod permissions;
+pub mod pod;
+pub mod pointer_path;
 pub mod process;
+pub mod procfs;
 pub mod remote_ptr;
+pub mod remote_struct;
+pub mod signature;
+pub mod snapshot;
+pub mod title;
 pub mod types;
-pub mod ids;
+pub mod watcher;
+#[cfg(feature = "write")]
+pub mod write;
 
 #[cfg(test)]
 mod tests;
 
 use crate::{
-    error::Error,
-    process::{get_process_procs, GameProcess, ProcessMemory},
+    error::{Context, Error, ResultExt},
+    process::{GameProcess, ProcessInfo, ProcessMemory},
+    procfs::Procfs,
     remote_ptr::RemotePtr,
+    signature::Globals,
 };
 use std::{ffi::c_void, mem::MaybeUninit};
 
 pub mod entity_list {
-    pub const OFFSET: usize = 0x27780;
+    /// Relative to the executable's load address. Only correct for the build our memory dumps
+    /// were taken from, see [`crate::signature::Globals::locate`] for other builds and the
+    /// `[globals]` of the built-in layout for the same offset
+    pub const OFFSET: usize = 0xCBF780;
     pub const ELEMENT_COUNT: usize = i16::MAX as usize;
     pub const LENGTH: usize = ELEMENT_COUNT * 16;
 }
@@ -47,29 +72,30 @@ pub fn get_static_entity_list(
         return Err(Error::GameProcessClosed);
     }
 
+    let globals = Globals::locate(process)?;
+
     let mut lst: [MaybeUninit<EntityPtr>; entity_list::ELEMENT_COUNT] =
         unsafe { MaybeUninit::uninit().assume_init() };
 
+    let address = process.base_address.get() + globals.entity_list;
+
     unsafe {
-        process.read_mem_into_unsafe(
-            &mut lst,
-            process.base_address.get() + entity_list::OFFSET,
-            entity_list::LENGTH,
-        )?;
+        process
+            .read_mem_into_unsafe(&mut lst, address, entity_list::LENGTH)
+            .context(Context::Read {
+                structure: "entity list",
+                address,
+                size: entity_list::LENGTH,
+            })?;
         Ok(std::mem::transmute(lst))
     }
 }
 
 pub fn find_game_process(first_open: bool) -> Result<GameProcess, Error> {
-    let mut procs = get_process_procs()?.map(GameProcess::new);
-    procs
-        .find_map(|b| match b {
-            Ok(Some(b)) => Some(b),
-            _ => None,
-        })
-        .ok_or(if first_open {
-            Error::MissingGameProcess
-        } else {
-            Error::GameProcessClosed
-        })
+    Procfs::default().find_game_process(first_open)
+}
+
+/// Every running game, oldest first, see [`Procfs::list_games`]
+pub fn list_games() -> Result<Vec<ProcessInfo>, Error> {
+    Procfs::default().list_games()
 }
@@ -0,0 +1,221 @@
+use std::{fmt::Display, ops::Range, path::Path, str::FromStr};
+
+use crate::error::Error;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Permissions {
+    pub read: bool,
+    pub write: bool,
+    pub execute: bool,
+    /// `s` (shared) as opposed to `p` (private, copy on write)
+    pub shared: bool,
+}
+impl FromStr for Permissions {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = s.as_bytes();
+        if bytes.len() != 4 {
+            return Err(format!("Invalid permissions: {s}"));
+        }
+
+        fn flag(byte: u8, set: u8) -> Result<bool, String> {
+            match byte {
+                b'-' => Ok(false),
+                x if x == set => Ok(true),
+                x => Err(format!("Invalid permission flag: {}", x as char)),
+            }
+        }
+
+        Ok(Self {
+            read: flag(bytes[0], b'r')?,
+            write: flag(bytes[1], b'w')?,
+            execute: flag(bytes[2], b'x')?,
+            shared: match bytes[3] {
+                b's' => true,
+                b'p' => false,
+                x => return Err(format!("Invalid sharing flag: {}", x as char)),
+            },
+        })
+    }
+}
+impl Display for Permissions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let flag = |set, c| if set { c } else { '-' };
+
+        write!(
+            f,
+            "{}{}{}{}",
+            flag(self.read, 'r'),
+            flag(self.write, 'w'),
+            flag(self.execute, 'x'),
+            if self.shared { 's' } else { 'p' }
+        )
+    }
+}
+
+/// A single line of `/proc/<pid>/maps`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryMapping {
+    pub range: Range<usize>,
+    pub permissions: Permissions,
+    /// Offset into the mapped file
+    pub offset: usize,
+    pub device: (u32, u32),
+    pub inode: u64,
+    /// File path, or pseudo paths like `[heap]`. `None` for anonymous mappings
+    pub pathname: Option<String>,
+}
+impl MemoryMapping {
+    pub fn len(&self) -> usize {
+        self.range.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.range.is_empty()
+    }
+
+    pub fn contains(&self, address: usize) -> bool {
+        self.range.contains(&address)
+    }
+
+    pub fn is_backed_by(&self, path: &Path) -> bool {
+        self.pathname.as_deref().map(Path::new) == Some(path)
+    }
+}
+impl FromStr for MemoryMapping {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut rest = line;
+
+        let mut next_field = |name: &str| {
+            let trimmed = rest.trim_start();
+            let end = trimmed.find(' ').unwrap_or(trimmed.len());
+            let (field, tail) = trimmed.split_at(end);
+            rest = tail;
+
+            if field.is_empty() {
+                Err(format!("Missing {name}"))
+            } else {
+                Ok(field)
+            }
+        };
+
+        fn hex(s: &str, name: &str) -> Result<usize, String> {
+            usize::from_str_radix(s, 16).map_err(|e| format!("Invalid {name} '{s}': {e}"))
+        }
+
+        let range = {
+            let field = next_field("address range")?;
+            let (start, end) = field
+                .split_once('-')
+                .ok_or_else(|| format!("Invalid address range: {field}"))?;
+
+            let start = hex(start, "range start")?;
+            let end = hex(end, "range end")?;
+            if end < start {
+                return Err(format!("Range end before start: {field}"));
+            }
+
+            start..end
+        };
+
+        let permissions = next_field("permissions")?.parse()?;
+        let offset = hex(next_field("offset")?, "offset")?;
+
+        let device = {
+            let field = next_field("device")?;
+            let (major, minor) = field
+                .split_once(':')
+                .ok_or_else(|| format!("Invalid device: {field}"))?;
+
+            let major = u32::from_str_radix(major, 16)
+                .map_err(|e| format!("Invalid device '{field}': {e}"))?;
+            let minor = u32::from_str_radix(minor, 16)
+                .map_err(|e| format!("Invalid device '{field}': {e}"))?;
+
+            (major, minor)
+        };
+
+        let inode = {
+            let field = next_field("inode")?;
+            field
+                .parse()
+                .map_err(|e| format!("Invalid inode '{field}': {e}"))?
+        };
+
+        // The path is padded to a column and may itself contain spaces
+        let pathname = Some(rest.trim())
+            .filter(|x| !x.is_empty())
+            .map(String::from);
+
+        Ok(Self {
+            range,
+            permissions,
+            offset,
+            device,
+            inode,
+            pathname,
+        })
+    }
+}
+
+/// Parsed contents of `/proc/<pid>/maps`, ordered by address
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryMap {
+    mappings: Vec<MemoryMapping>,
+}
+impl MemoryMap {
+    pub fn read(proc_path: &Path) -> Result<Self, Error> {
+        std::fs::read_to_string(proc_path.join("maps"))?.parse()
+    }
+
+    pub fn mappings(&self) -> &[MemoryMapping] {
+        &self.mappings
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &MemoryMapping> {
+        self.mappings.iter()
+    }
+
+    /// All mappings backed by the file at `path`
+    pub fn mappings_of<'a>(&'a self, path: &'a Path) -> impl Iterator<Item = &'a MemoryMapping> {
+        self.iter().filter(move |x| x.is_backed_by(path))
+    }
+
+    /// Lowest mapping of the file at `path`, i.e. the load address for an executable
+    pub fn first_mapping_of(&self, path: &Path) -> Option<&MemoryMapping> {
+        self.iter().find(|x| x.is_backed_by(path))
+    }
+
+    pub fn writable(&self) -> impl Iterator<Item = &MemoryMapping> {
+        self.iter().filter(|x| x.permissions.write)
+    }
+
+    pub fn containing(&self, address: usize) -> Option<&MemoryMapping> {
+        let index = self.mappings.partition_point(|x| x.range.end <= address);
+
+        self.mappings.get(index).filter(|x| x.contains(address))
+    }
+}
+impl FromStr for MemoryMap {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut mappings = s
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                line.parse()
+                    .map_err(|msg| Error::InvalidMemoryMap { line: i + 1, msg })
+            })
+            .collect::<Result<Vec<MemoryMapping>, Error>>()?;
+
+        // The kernel already sorts these, but `containing` relies on it
+        mappings.sort_by_key(|x| x.range.start);
+
+        Ok(Self { mappings })
+    }
+}
@@ -0,0 +1,252 @@
+use std::{
+    fmt::{Debug, Display},
+    str::FromStr,
+};
+
+use crate::{
+    error::Error,
+    ids::{
+        alignment::{Alignment, AlignmentMask},
+        classes::Class,
+        enemy_ally::EnemyAlly,
+        gender::Gender,
+        general::General,
+        race::Race,
+    },
+    types::{CAIObjectType, Lookup},
+};
+
+/// Object specifier as written in scripts, either `[EA.GENERAL.RACE.CLASS.SPECIFIC.GENDER.ALIGN]`
+/// or a quoted script name, e.g. `[ENEMY.HUMANOID.0.MAGE_ALL]` or `"Imoen"`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ObjectSpec {
+    Ids(ObjectIds),
+    Name(String),
+}
+impl ObjectSpec {
+    pub fn matches(&self, object: &CAIObjectType) -> bool {
+        match self {
+            Self::Ids(ids) => ids.matches(object),
+            Self::Name(name) => object
+                .name
+                .as_deref()
+                .is_some_and(|x| x.eq_ignore_ascii_case(name)),
+        }
+    }
+}
+
+/// Identifier fields of an [`ObjectSpec`] as raw IDS values, where 0 matches anything
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ObjectIds {
+    pub enemy_ally: u8,
+    pub general: u8,
+    pub race: u8,
+    pub class: u8,
+    pub specific: u8,
+    pub gender: u8,
+    pub alignment: u8,
+}
+impl ObjectIds {
+    pub fn matches(&self, object: &CAIObjectType) -> bool {
+        field(
+            self.enemy_ally,
+            object.enemy_ally.raw(),
+            EnemyAlly::includes,
+        ) && field(self.general, object.general.raw(), |x: General, y| {
+            x as u8 == y
+        }) && field(self.race, object.race.raw(), |x: Race, y| x as u8 == y)
+            && field(self.class, object.class.raw(), Class::includes)
+            && field(self.specific, object.specifics, |x: u8, y| x == y)
+            && field(self.gender, object.gender.raw(), |x: Gender, y| {
+                x as u8 == y
+            })
+            && field(
+                self.alignment,
+                object.alignment.raw(),
+                AlignmentMask::includes,
+            )
+    }
+
+    fn fields(&self) -> [u8; 7] {
+        [
+            self.enemy_ally,
+            self.general,
+            self.race,
+            self.class,
+            self.specific,
+            self.gender,
+            self.alignment,
+        ]
+    }
+}
+
+/// Whether `value` is one of `spec`, by `includes` if `spec` is one of `T` and as is otherwise
+fn field<T: TryFrom<u8>>(spec: u8, value: u8, includes: impl Fn(T, u8) -> bool) -> bool {
+    if spec == 0 {
+        return true;
+    }
+
+    match Lookup::<T, u8>::decode(spec) {
+        Lookup::Found(x) => includes(x, value),
+        Lookup::Unknown(x) => x == value,
+    }
+}
+
+impl CAIObjectType {
+    pub fn matches(&self, spec: &ObjectSpec) -> bool {
+        spec.matches(self)
+    }
+}
+
+/// Compares names without case or underscores, so `MAGE_ALL` finds [`Class::MageAll`]
+fn normalise(name: &str) -> String {
+    name.chars()
+        .filter(|x| *x != '_')
+        .map(|x| x.to_ascii_uppercase())
+        .collect()
+}
+
+fn by_name<T: Copy + Debug + Into<u8>>(variants: &[T], name: &str) -> Option<u8> {
+    let name = normalise(name);
+    variants
+        .iter()
+        .find(|x| normalise(&format!("{x:?}")) == name)
+        .map(|x| (*x).into())
+}
+
+/// IDS name of `value`, e.g. `MAGE_ALL` for [`Class::MageAll`], or the number if it has none
+fn ids_name<T: Debug + TryFrom<u8>>(value: u8) -> String {
+    match T::try_from(value) {
+        Ok(x) => {
+            let mut name = String::new();
+            let mut lower = false;
+            for c in format!("{x:?}").chars() {
+                if c.is_ascii_uppercase() && lower {
+                    name.push('_');
+                }
+                lower = c.is_ascii_lowercase();
+                name.push(c.to_ascii_uppercase());
+            }
+            name
+        }
+        Err(_) => value.to_string(),
+    }
+}
+
+/// Alignments take the names of masks when they leave out an axis, e.g. `MASK_EVIL`
+fn alignment_name(value: u8) -> String {
+    AlignmentMask::NAMES
+        .iter()
+        .find(|(_, x)| u8::from(*x) == value)
+        .map_or_else(|| ids_name::<Alignment>(value), |(x, _)| x.to_string())
+}
+
+impl Display for ObjectSpec {
+    /// Leaves out trailing fields which match anything, e.g. `[ENEMY.HUMANOID]`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ids = match self {
+            Self::Ids(ids) => ids,
+            Self::Name(name) => return write!(f, "\"{name}\""),
+        };
+
+        let fields = ids.fields();
+        let len = fields.iter().rposition(|x| *x != 0).map_or(1, |x| x + 1);
+        let names: [fn(u8) -> String; 7] = [
+            ids_name::<EnemyAlly>,
+            ids_name::<General>,
+            ids_name::<Race>,
+            ids_name::<Class>,
+            |x: u8| x.to_string(),
+            ids_name::<Gender>,
+            alignment_name,
+        ];
+
+        let fields = fields
+            .iter()
+            .zip(names)
+            .take(len)
+            .map(|(value, name)| {
+                if *value == 0 {
+                    "0".into()
+                } else {
+                    name(*value)
+                }
+            })
+            .collect::<Vec<_>>();
+        write!(f, "[{}]", fields.join("."))
+    }
+}
+
+impl FromStr for ObjectSpec {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = |msg: String| Error::InvalidObjectSpec {
+            spec: s.into(),
+            msg,
+        };
+        let trimmed = s.trim();
+
+        if let Some(name) = trimmed.strip_prefix('"').and_then(|x| x.strip_suffix('"')) {
+            return Ok(Self::Name(name.into()));
+        }
+
+        let Some(fields) = trimmed.strip_prefix('[').and_then(|x| x.strip_suffix(']')) else {
+            return Err(error("expected `[...]` or a quoted name".into()));
+        };
+
+        let fields = fields.split('.').map(str::trim).collect::<Vec<_>>();
+        if fields.len() > 7 {
+            return Err(error(format!("{} fields, at most 7 allowed", fields.len())));
+        }
+
+        let lookups: [fn(&str) -> Option<u8>; 7] = [
+            |x| by_name(EnemyAlly::VARIANTS, x),
+            |x| by_name(General::VARIANTS, x),
+            |x| by_name(Race::VARIANTS, x),
+            |x| by_name(Class::VARIANTS, x),
+            |_| None,
+            |x| by_name(Gender::VARIANTS, x),
+            |x| {
+                by_name(Alignment::VARIANTS, x).or_else(|| {
+                    let name = normalise(x);
+                    AlignmentMask::NAMES
+                        .iter()
+                        .find(|(x, _)| normalise(x) == name)
+                        .map(|(_, x)| (*x).into())
+                })
+            },
+        ];
+
+        let mut values = [0u8; 7];
+        for (i, field) in fields.iter().enumerate() {
+            values[i] = match field.parse() {
+                Ok(x) => x,
+                Err(_) if field.is_empty() => {
+                    return Err(error(format!("field {} is empty", i + 1)));
+                }
+                Err(_) => lookups[i](field)
+                    .ok_or_else(|| error(format!("unknown name `{field}` in field {}", i + 1)))?,
+            };
+        }
+
+        let [
+            enemy_ally,
+            general,
+            race,
+            class,
+            specific,
+            gender,
+            alignment,
+        ] = values;
+        Ok(Self::Ids(ObjectIds {
+            enemy_ally,
+            general,
+            race,
+            class,
+            specific,
+            gender,
+            alignment,
+        }))
+    }
+}
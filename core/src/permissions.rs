@@ -0,0 +1,128 @@
+use std::{fmt::Display, fs::read_to_string, num::NonZeroU32, path::Path};
+
+use crate::procfs::status_field;
+
+const PTRACE_SCOPE_PATH: &str = "/proc/sys/kernel/yama/ptrace_scope";
+/// Bit of `CAP_SYS_PTRACE` in the capability sets of `/proc/<pid>/status`
+const CAP_SYS_PTRACE: u32 = 19;
+
+/// Why reading another process's memory was refused, gathered after every backend failed
+#[derive(Debug)]
+pub struct PermissionDiagnostics {
+    pub pid: NonZeroU32,
+    /// Errors from each backend that was tried, by backend name
+    pub errors: Vec<(&'static str, std::io::Error)>,
+    /// `None` when Yama isn't enabled
+    pub ptrace_scope: Option<u32>,
+    pub has_cap_sys_ptrace: bool,
+    pub own_uid: u32,
+    /// Real uid of the game, `None` when its status couldn't be read
+    pub target_uid: Option<u32>,
+}
+impl PermissionDiagnostics {
+    pub fn gather(
+        path: &Path,
+        pid: NonZeroU32,
+        errors: Vec<(&'static str, std::io::Error)>,
+    ) -> Self {
+        let ptrace_scope = read_to_string(PTRACE_SCOPE_PATH)
+            .ok()
+            .and_then(|x| x.trim().parse().ok());
+
+        let has_cap_sys_ptrace = read_to_string("/proc/self/status")
+            .ok()
+            .and_then(|x| parse_effective_capabilities(&x))
+            .is_some_and(|caps| caps & (1 << CAP_SYS_PTRACE) != 0);
+
+        let target_uid = read_to_string(path.join("status"))
+            .ok()
+            .and_then(|x| parse_real_uid(&x));
+
+        Self {
+            pid,
+            errors,
+            ptrace_scope,
+            has_cap_sys_ptrace,
+            own_uid: unsafe { libc::geteuid() },
+            target_uid,
+        }
+    }
+
+    fn is_other_user(&self) -> bool {
+        self.target_uid.is_some_and(|x| x != self.own_uid)
+    }
+}
+impl Display for PermissionDiagnostics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Not permitted to read the memory of process {}",
+            self.pid
+        )?;
+        for (backend, error) in &self.errors {
+            write!(f, "\n  {backend}: {error}")?;
+        }
+
+        writeln!(f, "\n\nLikely cause:")?;
+
+        if self.own_uid == 0 || self.has_cap_sys_ptrace {
+            return write!(
+                f,
+                "  Running with CAP_SYS_PTRACE, so ptrace_scope and user checks don't apply.\n  \
+                 A security module (SELinux, AppArmor) may be denying access, or the game may have \
+                 made itself undumpable."
+            );
+        }
+
+        if let Some(target_uid) = self.target_uid
+            && self.is_other_user()
+        {
+            writeln!(
+                f,
+                "  The game runs as uid {target_uid} but this process runs as uid {}.\n  \
+                 Fix: run this as the same user as the game, or as root.",
+                self.own_uid
+            )?;
+        }
+
+        match self.ptrace_scope {
+            Some(1) => write!(
+                f,
+                "  Yama ptrace_scope is 1, so only a parent of the game may read its memory.\n  \
+                 Fix: run `sudo sysctl kernel.yama.ptrace_scope=0`, or grant the capability with \
+                 `sudo setcap cap_sys_ptrace=eip <path to this binary>`."
+            ),
+            Some(2) => write!(
+                f,
+                "  Yama ptrace_scope is 2, so reading another process's memory needs \
+                 CAP_SYS_PTRACE.\n  \
+                 Fix: run as root, or `sudo setcap cap_sys_ptrace=eip <path to this binary>`."
+            ),
+            Some(3) => write!(
+                f,
+                "  Yama ptrace_scope is 3, which disables reading other processes entirely.\n  \
+                 Fix: set kernel.yama.ptrace_scope to 0 or 1 in /etc/sysctl.d and reboot, as \
+                 it can't be lowered at runtime."
+            ),
+            _ if self.is_other_user() => Ok(()),
+            _ => write!(
+                f,
+                "  Neither ptrace_scope nor the user should block access. A security module \
+                 (SELinux, AppArmor) may be denying it, or the game may have made itself \
+                 undumpable."
+            ),
+        }
+    }
+}
+
+/// `CapEff` of a `/proc/<pid>/status` file
+pub(crate) fn parse_effective_capabilities(status: &str) -> Option<u64> {
+    status_field(status, "CapEff").and_then(|x| u64::from_str_radix(x.trim(), 16).ok())
+}
+
+/// First value of `Uid` in a `/proc/<pid>/status` file
+pub(crate) fn parse_real_uid(status: &str) -> Option<u32> {
+    status_field(status, "Uid")
+        .and_then(|x| x.split_whitespace().next())
+        .and_then(|x| x.parse().ok())
+}
@@ -0,0 +1,24 @@
+use crate::remote_ptr::RemotePtr;
+
+/// Plain old data: types which can be read straight out of another process's memory.
+///
+/// Enums aren't, as an unknown discriminant (e.g. an opcode added by a mod) would be undefined
+/// behaviour. Read their integer representation and decode it with
+/// [`Lookup::decode`](crate::types::Lookup::decode) instead.
+///
+/// # Safety
+///
+/// Every bit pattern of `size_of::<Self>()` bytes must be a valid value, which rules out enums,
+/// `bool`, `char` and references.
+pub unsafe trait Pod: Copy {}
+
+macro_rules! pod {
+    ($($type: ty),+) => {
+        $(unsafe impl Pod for $type {})+
+    };
+}
+pod!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+unsafe impl<T> Pod for *const T {}
+unsafe impl<T> Pod for RemotePtr<T> {}
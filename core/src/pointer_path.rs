@@ -0,0 +1,410 @@
+use std::{ffi::CStr, fmt::Display, str::FromStr};
+
+use serde::Deserialize;
+
+use crate::{
+    error::{Context, Error, ResultExt},
+    process::ProcessMemory,
+    remote_ptr::RemotePtr,
+};
+
+/// Address a [`PointerPath`] starts from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathStart {
+    /// Load address of the game's executable
+    Base,
+    Address(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathStep {
+    /// `+0x10` or `-0x10`, moves the address
+    Offset(isize),
+    /// `[0x10]`, follows the pointer at this offset from the address
+    Deref(isize),
+}
+
+/// Largest `cstr(n)` or `bytes(n)`, so a typo in a path can't make the watcher read gigabytes
+/// every update
+pub const MAX_READ_SIZE: usize = 0x10000;
+
+/// How the address a [`PointerPath`] ends at is read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathRead {
+    U8,
+    U16,
+    U32,
+    U64,
+    I8,
+    I16,
+    I32,
+    I64,
+    F32,
+    F64,
+    Ptr,
+    /// NUL terminated text of at most this many bytes
+    CStr(usize),
+    Bytes(usize),
+}
+impl PathRead {
+    const NAMES: &[(&str, PathRead)] = &[
+        ("u8", Self::U8),
+        ("u16", Self::U16),
+        ("u32", Self::U32),
+        ("u64", Self::U64),
+        ("i8", Self::I8),
+        ("i16", Self::I16),
+        ("i32", Self::I32),
+        ("i64", Self::I64),
+        ("f32", Self::F32),
+        ("f64", Self::F64),
+        ("ptr", Self::Ptr),
+    ];
+
+    pub fn size(&self) -> usize {
+        match self {
+            Self::U8 | Self::I8 => 1,
+            Self::U16 | Self::I16 => 2,
+            Self::U32 | Self::I32 | Self::F32 => 4,
+            Self::U64 | Self::I64 | Self::F64 => 8,
+            Self::Ptr => size_of::<usize>(),
+            Self::CStr(size) | Self::Bytes(size) => *size,
+        }
+    }
+
+    fn decode(&self, bytes: &[u8]) -> PathValue {
+        macro_rules! int {
+            ($variant: ident, $ty: ty) => {
+                PathValue::$variant(<$ty>::from_ne_bytes(bytes.try_into().unwrap()).into())
+            };
+        }
+
+        match self {
+            Self::U8 => int!(Unsigned, u8),
+            Self::U16 => int!(Unsigned, u16),
+            Self::U32 => int!(Unsigned, u32),
+            Self::U64 => int!(Unsigned, u64),
+            Self::I8 => int!(Signed, i8),
+            Self::I16 => int!(Signed, i16),
+            Self::I32 => int!(Signed, i32),
+            Self::I64 => int!(Signed, i64),
+            Self::F32 => int!(Float, f32),
+            Self::F64 => int!(Float, f64),
+            Self::Ptr => PathValue::Address(usize::from_ne_bytes(bytes.try_into().unwrap())),
+            Self::CStr(_) => PathValue::Text(
+                CStr::from_bytes_until_nul(bytes)
+                    .ok()
+                    .map(|x| x.to_string_lossy().into_owned()),
+            ),
+            Self::Bytes(_) => PathValue::Bytes(bytes.to_vec()),
+        }
+    }
+}
+impl Display for PathRead {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CStr(size) => write!(f, "cstr({size})"),
+            Self::Bytes(size) => write!(f, "bytes({size})"),
+            x => {
+                let (name, _) = Self::NAMES.iter().find(|(_, read)| read == x).unwrap();
+                write!(f, "{name}")
+            }
+        }
+    }
+}
+
+/// Result of evaluating a [`PointerPath`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathValue {
+    /// Where the path ends when it has no read, or a pointer read with `ptr`
+    Address(usize),
+    Unsigned(u64),
+    Signed(i64),
+    Float(f64),
+    /// `None` if there's no NUL within the size
+    Text(Option<String>),
+    Bytes(Vec<u8>),
+}
+impl Display for PathValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Address(x) => write!(f, "{x:#x}"),
+            Self::Unsigned(x) => write!(f, "{x} ({x:#x})"),
+            Self::Signed(x) => write!(f, "{x}"),
+            Self::Float(x) => write!(f, "{x}"),
+            Self::Text(Some(x)) => write!(f, "{x:?}"),
+            Self::Text(None) => write!(f, "<unterminated>"),
+            Self::Bytes(x) => write!(f, "{x:02x?}"),
+        }
+    }
+}
+
+/// Chain of offsets and dereferences for exploring the game's memory, written like
+/// `base+0xCBF780 -> [8] -> [0x3910] -> cstr(64)` for the name of the creature in the first slot
+/// of the entity list:
+///
+/// - `base` or an address to start from
+/// - `+n` / `-n` to move the address
+/// - `[n]` to follow the pointer `n` bytes from the address
+/// - optionally a read to end with, one of `u8` to `u64`, `i8` to `i64`, `f32`, `f64`, `ptr`,
+///   `cstr(n)` or `bytes(n)`, with `n` up to [`MAX_READ_SIZE`]. Without one the path evaluates to
+///   its final address
+///
+/// `->` only separates steps for readability. Numbers are decimal or `0x` prefixed hex.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct PointerPath {
+    pub start: PathStart,
+    pub steps: Vec<PathStep>,
+    pub read: Option<PathRead>,
+}
+impl PointerPath {
+    /// Follows the path in `process`, with `base` as the address `base` stands for
+    pub fn eval(
+        &self,
+        process: impl ProcessMemory + Copy,
+        base: usize,
+    ) -> Result<PathValue, Error> {
+        let mut address = match self.start {
+            PathStart::Base => base,
+            PathStart::Address(x) => x,
+        };
+
+        for (index, step) in self.steps.iter().enumerate() {
+            address = match *step {
+                PathStep::Offset(offset) => address.wrapping_add_signed(offset),
+                PathStep::Deref(offset) => {
+                    let ptr = RemotePtr::new(address.wrapping_add_signed(offset) as *const usize);
+
+                    ptr.read(process)
+                        .context(Context::Read {
+                            structure: "pointer",
+                            address: ptr.addr(),
+                            size: size_of::<usize>(),
+                        })
+                        .context(Context::PathStep {
+                            index,
+                            step: step.to_string(),
+                        })?
+                }
+            };
+        }
+
+        let Some(read) = self.read else {
+            return Ok(PathValue::Address(address));
+        };
+
+        process
+            .read_mem(address, read.size())
+            .map(|x| read.decode(&x))
+            .context(Context::Read {
+                structure: "value",
+                address,
+                size: read.size(),
+            })
+            .context(Context::PathStep {
+                index: self.steps.len(),
+                step: read.to_string(),
+            })
+    }
+}
+impl Display for PathStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Offset(x) if *x < 0 => write!(f, "-{:#x}", x.unsigned_abs()),
+            Self::Offset(x) => write!(f, "+{x:#x}"),
+            Self::Deref(x) if *x < 0 => write!(f, "[-{:#x}]", x.unsigned_abs()),
+            Self::Deref(x) => write!(f, "[{x:#x}]"),
+        }
+    }
+}
+impl Display for PointerPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.start {
+            PathStart::Base => write!(f, "base")?,
+            PathStart::Address(x) => write!(f, "{x:#x}")?,
+        }
+
+        for step in &self.steps {
+            match step {
+                PathStep::Offset(_) => write!(f, "{step}")?,
+                PathStep::Deref(_) => write!(f, " -> {step}")?,
+            }
+        }
+
+        if let Some(read) = self.read {
+            write!(f, " -> {read}")?;
+        }
+
+        Ok(())
+    }
+}
+impl TryFrom<String> for PointerPath {
+    type Error = Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+impl FromStr for PointerPath {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Parser { path: s, rest: s }.parse()
+    }
+}
+
+struct Parser<'a> {
+    path: &'a str,
+    rest: &'a str,
+}
+impl<'a> Parser<'a> {
+    fn error(&self, msg: impl Into<String>) -> Error {
+        Error::InvalidPointerPath {
+            path: self.path.into(),
+            msg: msg.into(),
+        }
+    }
+
+    fn skip_separators(&mut self) {
+        loop {
+            self.rest = self.rest.trim_start();
+
+            match self.rest.strip_prefix("->") {
+                Some(rest) => self.rest = rest,
+                None => break,
+            }
+        }
+    }
+
+    fn eat(&mut self, prefix: char) -> bool {
+        self.rest
+            .strip_prefix(prefix)
+            .map(|rest| self.rest = rest)
+            .is_some()
+    }
+
+    /// Up to the next character which can't be in a number or name
+    fn word(&mut self) -> &'a str {
+        let end = self
+            .rest
+            .find(|x: char| !x.is_ascii_alphanumeric() && x != '_')
+            .unwrap_or(self.rest.len());
+        let (word, rest) = self.rest.split_at(end);
+        self.rest = rest;
+
+        word
+    }
+
+    fn number(&mut self) -> Result<usize, Error> {
+        self.rest = self.rest.trim_start();
+        let word = self.word();
+
+        let number = match word.strip_prefix("0x").or_else(|| word.strip_prefix("0X")) {
+            Some(hex) => usize::from_str_radix(hex, 16),
+            None => word.parse(),
+        };
+
+        number.map_err(|_| match word {
+            "" => self.error("expected a number"),
+            x => self.error(format!("'{x}' is not a number")),
+        })
+    }
+
+    fn offset(&mut self) -> Result<isize, Error> {
+        self.rest = self.rest.trim_start();
+        let negative = self.eat('-');
+        if !negative {
+            self.eat('+');
+        }
+
+        let number = self.number()?;
+        let offset = isize::try_from(number)
+            .map_err(|_| self.error(format!("offset {number:#x} is too large")))?;
+
+        Ok(if negative { -offset } else { offset })
+    }
+
+    fn parse(mut self) -> Result<PointerPath, Error> {
+        self.rest = self.rest.trim_start();
+
+        let start = if self.rest.starts_with(|x: char| x.is_ascii_digit()) {
+            PathStart::Address(self.number()?)
+        } else {
+            match self.word() {
+                "base" => PathStart::Base,
+                "" => return Err(self.error("expected base or an address to start from")),
+                x => return Err(self.error(format!("'{x}' is not base or an address"))),
+            }
+        };
+
+        let mut steps = Vec::new();
+        let mut read = None;
+
+        loop {
+            self.skip_separators();
+
+            if self.rest.is_empty() {
+                break;
+            }
+
+            if read.is_some() {
+                return Err(self.error(format!("'{}' follows the read", self.rest)));
+            }
+
+            if self.rest.starts_with(['+', '-']) {
+                steps.push(PathStep::Offset(self.offset()?));
+            } else if self.eat('[') {
+                self.rest = self.rest.trim_start();
+                let offset = if self.rest.starts_with(']') {
+                    0
+                } else {
+                    self.offset()?
+                };
+
+                self.rest = self.rest.trim_start();
+                if !self.eat(']') {
+                    return Err(self.error("expected ']'"));
+                }
+
+                steps.push(PathStep::Deref(offset));
+            } else {
+                read = Some(self.read()?);
+            }
+        }
+
+        Ok(PointerPath { start, steps, read })
+    }
+
+    fn read(&mut self) -> Result<PathRead, Error> {
+        let name = self.word();
+
+        if let Some((_, read)) = PathRead::NAMES.iter().find(|(x, _)| *x == name) {
+            return Ok(*read);
+        }
+
+        let sized = match name {
+            "cstr" => PathRead::CStr,
+            "bytes" => PathRead::Bytes,
+            "" => return Err(self.error(format!("unexpected '{}'", self.rest))),
+            x => return Err(self.error(format!("'{x}' is not a step or a read"))),
+        };
+
+        self.rest = self.rest.trim_start();
+        if !self.eat('(') {
+            return Err(self.error(format!("{name} needs a size, e.g. {name}(64)")));
+        }
+        let size = self.number()?;
+        if size > MAX_READ_SIZE {
+            return Err(self.error(format!(
+                "{name}({size}) reads more than the limit of {MAX_READ_SIZE} bytes"
+            )));
+        }
+        self.rest = self.rest.trim_start();
+        if !self.eat(')') {
+            return Err(self.error("expected ')'"));
+        }
+
+        Ok(sized(size))
+    }
+}
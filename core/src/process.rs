@@ -1,10 +1,31 @@
 use std::{
-    fs::read_to_string,
+    fs::{File, read_to_string},
     num::{NonZero, NonZeroU32, NonZeroUsize},
-    path::PathBuf,
+    os::unix::fs::FileExt,
+    path::{Path, PathBuf},
+    time::SystemTime,
 };
 
-use crate::error::Error;
+use crate::{
+    build_id::BuildId,
+    error::Error,
+    language::Language,
+    layout::{Layout, LayoutDatabase},
+    memory_map::MemoryMap,
+    permissions::PermissionDiagnostics,
+    procfs::{PidNamespace, Procfs, is_alive, is_exited, start_time},
+    title::GameTitle,
+};
+
+/// Most iovecs a single `process_vm_readv` accepts
+const IOV_MAX: usize = libc::UIO_MAXIOV as usize;
+
+/// One region of a [`ProcessMemory::read_batch`], read into `buffer`
+#[derive(Debug)]
+pub struct ReadRequest<'a> {
+    pub address: usize,
+    pub buffer: &'a mut [u8],
+}
 
 pub trait ProcessMemory {
     fn read_mem(&self, address: usize, length: usize) -> Result<Vec<u8>, Error>;
@@ -20,16 +41,213 @@ pub trait ProcessMemory {
         address: usize,
         length: usize,
     ) -> Result<isize, Error>;
+
+    /// Reads several regions at once, filling each request's buffer
+    fn read_batch(&self, requests: &mut [ReadRequest<'_>]) -> Result<(), Error> {
+        requests.iter_mut().try_for_each(|request| {
+            let length = request.buffer.len();
+            self.read_mem_into(request.buffer, request.address, length)
+                .map(|_| ())
+        })
+    }
 }
 
-pub(crate) fn get_base_address_from_memory_map(maps: &str) -> Result<NonZeroUsize, Error> {
-    maps.trim_end()
-        .lines()
-        .nth(3)
-        .and_then(|x| Some(x.split_once('-')?.0))
-        .and_then(|x| usize::from_str_radix(x, 16).ok())
-        .and_then(NonZero::new)
-        .ok_or(Error::Memory("Could not get base address".into()))
+/// Load address of `exe`, i.e. the start of its lowest mapping
+pub(crate) fn get_base_address_from_memory_map(
+    maps: &MemoryMap,
+    exe: &Path,
+) -> Result<NonZeroUsize, Error> {
+    maps.first_mapping_of(exe)
+        .and_then(|x| NonZero::new(x.range.start))
+        .ok_or_else(|| {
+            Error::Memory(format!(
+                "Could not get base address: {} is not mapped",
+                exe.display()
+            ))
+        })
+}
+
+/// How the memory of a [`GameProcess`] is read
+#[derive(Debug)]
+pub enum MemoryBackend {
+    /// `process_vm_readv`, which can batch many reads into one syscall
+    ProcessVmReadv,
+    /// `pread` on `/proc/<pid>/mem`, for kernels or sandboxes without `process_vm_readv`
+    ProcMem(File),
+}
+impl MemoryBackend {
+    /// First backend able to read `probe_address`, preferring `process_vm_readv`
+    pub fn select(path: &Path, pid: NonZeroU32, probe_address: usize) -> Result<Self, Error> {
+        let mut probe = [0u8; 1];
+
+        let vm_readv_error = match vm_readv(pid, probe.as_mut_ptr(), probe_address, probe.len()) {
+            Ok(_) => return Ok(Self::ProcessVmReadv),
+            Err(Error::Io(e)) if is_access_error(&e) => e,
+            Err(e) => return Err(e),
+        };
+
+        let proc_mem_error = match File::open(path.join("mem")).and_then(|file| {
+            file.read_exact_at(&mut probe, probe_address as u64)
+                .map(|_| file)
+        }) {
+            Ok(file) => return Ok(Self::ProcMem(file)),
+            Err(e) if is_access_error(&e) => e,
+            Err(e) => return Err(e.into()),
+        };
+
+        let errors = vec![
+            ("process_vm_readv", vm_readv_error),
+            ("/proc/<pid>/mem", proc_mem_error),
+        ];
+
+        if errors
+            .iter()
+            .all(|(_, e)| e.raw_os_error() == Some(libc::ENOSYS))
+        {
+            return Err(errors.into_iter().next().unwrap().1.into());
+        }
+
+        Err(Error::PermissionDenied(Box::new(
+            PermissionDiagnostics::gather(path, pid, errors),
+        )))
+    }
+}
+
+/// Errors after which another backend may still work
+fn is_access_error(e: &std::io::Error) -> bool {
+    matches!(
+        e.raw_os_error(),
+        Some(libc::EPERM | libc::EACCES | libc::ENOSYS)
+    )
+}
+
+fn vm_readv<T>(
+    pid: NonZeroU32,
+    buffer: *mut T,
+    address: usize,
+    length: usize,
+) -> Result<isize, Error> {
+    let src_iovec = libc::iovec {
+        iov_len: length,
+        iov_base: address as *mut libc::c_void,
+    };
+
+    let dst_iovec = libc::iovec {
+        iov_len: length,
+        iov_base: buffer as *mut libc::c_void,
+    };
+
+    let read = unsafe { libc::process_vm_readv(pid.get() as i32, &dst_iovec, 1, &src_iovec, 1, 0) };
+
+    if read != (length as isize) {
+        let err = std::io::Error::last_os_error();
+        Err(err.into())
+    } else {
+        Ok(read)
+    }
+}
+
+/// What procfs alone tells us about a game process, before touching its memory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessInfo {
+    pub path: PathBuf,
+    pub pid: NonZeroU32,
+    /// Name of the game executable, see [`GameTitle::from_executable_name`]
+    pub name: String,
+    pub title: GameTitle,
+    /// Path of the game executable, as seen from the game's own mount namespace
+    pub exe: PathBuf,
+    /// Where the game executable can be opened from this process
+    pub exe_file: PathBuf,
+    pub base_address: NonZeroUsize,
+    pub namespace: PidNamespace,
+    /// `None` if procfs doesn't say, e.g. without a readable `/proc/stat`
+    pub start_time: Option<SystemTime>,
+}
+impl ProcessInfo {
+    /// `None` if the process isn't a game or has exited.
+    ///
+    /// The game is matched by `comm` or by the name of its executable, as sandboxed launches
+    /// don't always keep `comm`. When started through the dynamic loader (as the Steam Runtime
+    /// does), the executable is found among the mapped files instead.
+    pub fn read((path, pid): (PathBuf, NonZeroU32)) -> Result<Option<Self>, Error> {
+        let comm = match read_to_string(path.join("comm")) {
+            Ok(x) => x.trim_end().to_string(),
+            Err(e) if is_exited(&e) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let exe_link = match std::fs::read_link(path.join("exe")) {
+            Ok(x) => x,
+            Err(e) if is_exited(&e) => return Ok(None),
+            Err(e) if is_game_name(&comm) => return Err(e.into()),
+            // e.g. processes of other users, which aren't the game going by `comm`
+            Err(_) => return Ok(None),
+        };
+
+        let is_game_exe = file_name(&exe_link).is_some_and(is_game_name);
+        let is_loader = file_name(&exe_link).is_some_and(|x| x.starts_with("ld-"));
+
+        if !(is_game_name(&comm) || is_game_exe || is_loader) || !is_alive(&path) {
+            return Ok(None);
+        }
+
+        let maps = match MemoryMap::read(&path) {
+            Err(Error::Io(e)) if is_exited(&e) => return Ok(None),
+            x => x?,
+        };
+
+        let (exe, exe_file) = if is_loader && !is_game_name(&comm) {
+            let Some(exe) = maps
+                .iter()
+                .filter_map(|x| x.pathname.as_deref().map(Path::new))
+                .find(|x| file_name(x).is_some_and(is_game_name))
+            else {
+                return Ok(None);
+            };
+
+            let exe_file = path.join("root").join(exe.strip_prefix("/").unwrap_or(exe));
+            (exe.to_path_buf(), exe_file)
+        } else {
+            (exe_link, path.join("exe"))
+        };
+
+        let name = file_name(&exe)
+            .filter(|x| is_game_name(x))
+            .unwrap_or(&comm)
+            .to_string();
+
+        let game_dir = exe
+            .parent()
+            .map(|x| path.join("root").join(x.strip_prefix("/").unwrap_or(x)));
+        let Some(title) = GameTitle::detect(&name, game_dir.as_deref().unwrap_or(&path)) else {
+            return Ok(None);
+        };
+
+        let base_address = get_base_address_from_memory_map(&maps, &exe)?;
+        let namespace = PidNamespace::read(&path);
+        let start_time = start_time(&path);
+
+        Ok(Some(Self {
+            path,
+            pid,
+            name,
+            title,
+            exe,
+            exe_file,
+            base_address,
+            namespace,
+            start_time,
+        }))
+    }
+}
+
+fn is_game_name(name: &str) -> bool {
+    GameTitle::from_executable_name(name).is_some()
+}
+
+fn file_name(path: &Path) -> Option<&str> {
+    path.file_name().and_then(|x| x.to_str())
 }
 
 #[derive(Debug)]
@@ -38,29 +256,65 @@ pub struct GameProcess {
     pub pid: NonZeroU32,
     pub base_address: NonZeroUsize,
     pub name: String,
+    pub title: GameTitle,
+    /// Language strings are decoded with, detected on attach. Set it if detection picks wrong
+    pub language: Language,
+    /// Path of the game executable, see [`ProcessInfo::exe`]
+    pub exe: PathBuf,
+    pub build_id: BuildId,
+    /// Structure offsets for this build of the game
+    pub layout: Layout,
+    pub backend: MemoryBackend,
+    /// Namespace the game was found through. Reads always use `pid`, its pid in our procfs
+    pub namespace: PidNamespace,
 }
 impl GameProcess {
+    /// Whether the game is still running
     pub fn exists(&self) -> bool {
-        self.path.try_exists().unwrap_or(false)
+        is_alive(&self.path)
     }
 
-    pub fn new((path, pid): (PathBuf, NonZeroU32)) -> Result<Option<Self>, Error> {
-        let name = read_to_string(path.join("comm"))?.trim_end().to_string();
-
-        if !["BaldursGate", "BaldursGateII"].contains(&name.as_str()) {
-            return Ok(None);
-        }
+    /// Current memory map of the process. Not cached, as the heap mappings change as the game runs
+    pub fn memory_map(&self) -> Result<MemoryMap, Error> {
+        MemoryMap::read(&self.path)
+    }
 
-        let maps = read_to_string(path.join("maps"))?;
+    pub fn new(proc: (PathBuf, NonZeroU32)) -> Result<Option<Self>, Error> {
+        ProcessInfo::read(proc)?.map(Self::attach).transpose()
+    }
 
-        let base_address = get_base_address_from_memory_map(&maps)?;
+    /// Identifies the build and picks a memory backend for the game found in `info`
+    pub fn attach(
+        ProcessInfo {
+            path,
+            pid,
+            name,
+            title,
+            exe,
+            exe_file,
+            base_address,
+            namespace,
+            ..
+        }: ProcessInfo,
+    ) -> Result<Self, Error> {
+        let build_id = BuildId::for_executable(&exe_file)?;
+        let layout = LayoutDatabase::shared()?.select(title, &build_id)?.clone();
+        let backend = MemoryBackend::select(&path, pid, base_address.get())?;
+        let language = Language::detect(&path).unwrap_or_default();
 
-        Ok(Some(Self {
+        Ok(Self {
             path,
             name,
+            title,
+            language,
             pid,
             base_address,
-        }))
+            exe,
+            build_id,
+            layout,
+            backend,
+            namespace,
+        })
     }
 }
 
@@ -71,26 +325,13 @@ impl ProcessMemory for &GameProcess {
         address: usize,
         length: usize,
     ) -> Result<isize, Error> {
-        let pid = self.pid.get() as i32;
-
-        unsafe {
-            let src_iovec = libc::iovec {
-                iov_len: length,
-                iov_base: address as *mut libc::c_void,
-            };
+        match &self.backend {
+            MemoryBackend::ProcessVmReadv => vm_readv(self.pid, buffer, address, length),
+            MemoryBackend::ProcMem(file) => {
+                let buffer = unsafe { std::slice::from_raw_parts_mut(buffer.cast::<u8>(), length) };
+                file.read_exact_at(buffer, address as u64)?;
 
-            let dst_iovec = libc::iovec {
-                iov_len: length,
-                iov_base: buffer as *mut libc::c_void,
-            };
-
-            let read = libc::process_vm_readv(pid, &dst_iovec, 1, &src_iovec, 1, 0);
-
-            if read != (length as isize) {
-                let err = std::io::Error::last_os_error();
-                Err(err.into())
-            } else {
-                Ok(read)
+                Ok(length as isize)
             }
         }
     }
@@ -118,29 +359,91 @@ impl ProcessMemory for &GameProcess {
         self.read_mem_into(dst_buf.as_mut_slice(), address, length)
             .map(|_| dst_buf)
     }
+
+    /// One `process_vm_readv` per [`IOV_MAX`] requests, or one `pread` per request
+    fn read_batch(&self, requests: &mut [ReadRequest<'_>]) -> Result<(), Error> {
+        let file = match &self.backend {
+            MemoryBackend::ProcessVmReadv => return vm_readv_batch(self.pid, requests),
+            MemoryBackend::ProcMem(file) => file,
+        };
+
+        // Kept as an io error, like process_vm_readv's, naming the request which failed
+        requests.iter_mut().try_for_each(|request| {
+            file.read_exact_at(request.buffer, request.address as u64)
+                .map_err(|e| {
+                    let msg = format!("Read failed at 0x{:x}: {e}", request.address);
+                    Error::Io(std::io::Error::new(e.kind(), msg))
+                })
+        })
+    }
 }
 
-pub fn get_process_procs()
--> Result<impl Iterator<Item = (std::path::PathBuf, NonZeroU32)>, std::io::Error> {
-    let proc = std::fs::read_dir("/proc")?;
+fn vm_readv_batch(pid: NonZeroU32, requests: &mut [ReadRequest<'_>]) -> Result<(), Error> {
+    let pid = pid.get() as i32;
 
-    Ok(proc.into_iter().filter_map(|entry| {
-        if let Ok(entry) = entry
-            && let Ok(metadata) = entry.metadata()
-        {
-            fn to_number(name: &std::ffi::OsString) -> Option<NonZeroU32> {
-                name.to_str().and_then(|name| name.parse().ok())
-            }
+    for chunk in requests.chunks_mut(IOV_MAX) {
+        let src_iovecs = chunk
+            .iter()
+            .map(|request| libc::iovec {
+                iov_len: request.buffer.len(),
+                iov_base: request.address as *mut libc::c_void,
+            })
+            .collect::<Vec<_>>();
 
-            if metadata.is_dir()
-                && let Some(pid) = to_number(&entry.file_name())
-            {
-                Some((entry.path(), pid))
-            } else {
-                None
-            }
-        } else {
-            None
+        let dst_iovecs = chunk
+            .iter_mut()
+            .map(|request| libc::iovec {
+                iov_len: request.buffer.len(),
+                iov_base: request.buffer.as_mut_ptr().cast(),
+            })
+            .collect::<Vec<_>>();
+
+        let expected: usize = chunk.iter().map(|x| x.buffer.len()).sum();
+
+        let read = unsafe {
+            libc::process_vm_readv(
+                pid,
+                dst_iovecs.as_ptr(),
+                dst_iovecs.len() as libc::c_ulong,
+                src_iovecs.as_ptr(),
+                src_iovecs.len() as libc::c_ulong,
+                0,
+            )
+        };
+
+        if read < 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+
+        if read as usize != expected {
+            // Reads stop at the first request which couldn't be read in full
+            let mut remaining = read as usize;
+            let failed = chunk
+                .iter()
+                .find(
+                    |request| match remaining.checked_sub(request.buffer.len()) {
+                        Some(x) => {
+                            remaining = x;
+                            false
+                        }
+                        None => true,
+                    },
+                )
+                .map(|request| request.address)
+                .unwrap_or_default();
+
+            return Err(Error::Io(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!("Partial read at 0x{failed:x}: read {read} of {expected} bytes"),
+            )));
         }
-    }))
+    }
+
+    Ok(())
+}
+
+/// Processes in `/proc`, see [`Procfs::processes`]
+pub fn get_process_procs()
+-> Result<impl Iterator<Item = (std::path::PathBuf, NonZeroU32)>, std::io::Error> {
+    Procfs::default().processes()
 }
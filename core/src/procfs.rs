@@ -0,0 +1,261 @@
+use std::{
+    fmt::Display,
+    fs::read_to_string,
+    num::NonZeroU32,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    error::Error,
+    process::{GameProcess, ProcessInfo},
+    title::GameTitle,
+};
+
+/// A procfs mount, normally `/proc`. Tests point this at a fixture tree with the same layout
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Procfs {
+    root: PathBuf,
+}
+impl Default for Procfs {
+    fn default() -> Self {
+        Self::new("/proc")
+    }
+}
+impl Procfs {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Directory and pid of every process
+    pub fn processes(
+        &self,
+    ) -> Result<impl Iterator<Item = (PathBuf, NonZeroU32)> + use<>, std::io::Error> {
+        let proc = std::fs::read_dir(&self.root)?;
+
+        Ok(proc.into_iter().filter_map(|entry| {
+            if let Ok(entry) = entry
+                && let Ok(metadata) = entry.metadata()
+            {
+                fn to_number(name: &std::ffi::OsString) -> Option<NonZeroU32> {
+                    name.to_str().and_then(|name| name.parse().ok())
+                }
+
+                if metadata.is_dir()
+                    && let Some(pid) = to_number(&entry.file_name())
+                {
+                    Some((entry.path(), pid))
+                } else {
+                    None
+                }
+            } else {
+                None
+            }
+        }))
+    }
+
+    /// Every running game, skipping processes which exit while being read
+    pub fn find_games(
+        &self,
+    ) -> Result<impl Iterator<Item = Result<ProcessInfo, Error>> + use<>, Error> {
+        Ok(self
+            .processes()?
+            .filter_map(|x| ProcessInfo::read(x).transpose()))
+    }
+
+    /// Every running game which could be read, oldest first and those whose start time is unknown
+    /// last. Use [`Procfs::find_games`] to see why the others couldn't
+    pub fn list_games(&self) -> Result<Vec<ProcessInfo>, Error> {
+        let mut games = self
+            .find_games()?
+            .filter_map(Result::ok)
+            .collect::<Vec<_>>();
+        games.sort_by_key(|x| (x.start_time.is_none(), x.start_time, x.pid));
+
+        Ok(games)
+    }
+
+    /// First game which can be attached to. If every game fails, returns the first error so
+    /// e.g. permission problems aren't reported as a missing game
+    pub fn find_game_process(&self, first_open: bool) -> Result<GameProcess, Error> {
+        self.find_game_process_by(&GameSelector::Any, first_open)
+    }
+
+    /// First game picked by `selector` which can be attached to, see
+    /// [`Procfs::find_game_process`]
+    pub fn find_game_process_by(
+        &self,
+        selector: &GameSelector,
+        first_open: bool,
+    ) -> Result<GameProcess, Error> {
+        let mut first_error = None;
+
+        let games = self
+            .find_games()?
+            .filter(|x| x.as_ref().map_or(true, |info| selector.matches(info)));
+
+        for info in games {
+            match info.and_then(GameProcess::attach) {
+                Ok(process) => return Ok(process),
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        Err(first_error.unwrap_or(if first_open {
+            Error::MissingGameProcess
+        } else {
+            Error::GameProcessClosed
+        }))
+    }
+}
+
+/// Which of several running games to attach to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GameSelector {
+    #[default]
+    Any,
+    Pid(NonZeroU32),
+    Title(GameTitle),
+}
+impl GameSelector {
+    pub fn matches(&self, info: &ProcessInfo) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Pid(pid) => info.pid == *pid,
+            Self::Title(title) => info.title == *title,
+        }
+    }
+}
+
+/// Pid namespace a process runs in, e.g. the sandbox of Flatpak Steam or pressure-vessel
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PidNamespace {
+    /// Inode of `/proc/<pid>/ns/pid`, which identifies the namespace. `None` if unreadable
+    pub inode: Option<u64>,
+    /// `NSpid` of the process: its pid in the namespace of the procfs mount first, then in each
+    /// nested namespace down to its own
+    pub pids: Vec<NonZeroU32>,
+}
+impl PidNamespace {
+    pub fn read(path: &Path) -> Self {
+        let inode = std::fs::read_link(path.join("ns").join("pid"))
+            .ok()
+            .and_then(|x| parse_namespace_inode(&x.to_string_lossy()));
+
+        let pids = read_to_string(path.join("status"))
+            .ok()
+            .map(|x| parse_nspid(&x))
+            .unwrap_or_default();
+
+        Self { inode, pids }
+    }
+
+    /// Whether the process is in a namespace nested below the one procfs was mounted for
+    pub fn is_nested(&self) -> bool {
+        self.pids.len() > 1
+    }
+
+    /// Pid of the process as it sees itself
+    pub fn local_pid(&self) -> Option<NonZeroU32> {
+        self.pids.last().copied()
+    }
+}
+impl Display for PidNamespace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inode = self
+            .inode
+            .map(|x| x.to_string())
+            .unwrap_or_else(|| "unknown".into());
+
+        match self.local_pid() {
+            Some(pid) if self.is_nested() => {
+                write!(f, "nested pid namespace {inode} as pid {pid}")
+            }
+            _ => write!(f, "pid namespace {inode}"),
+        }
+    }
+}
+
+/// Whether the process at `path` is still running, i.e. exists and isn't a zombie
+pub fn is_alive(path: &Path) -> bool {
+    read_to_string(path.join("status"))
+        .ok()
+        .and_then(|status| {
+            status_field(&status, "State").and_then(|state| state.trim_start().chars().next())
+        })
+        .is_some_and(|state| !matches!(state, 'Z' | 'X'))
+}
+
+/// When the process at `path` started, `None` if its stat or the boot time can't be read
+pub fn start_time(path: &Path) -> Option<SystemTime> {
+    let ticks = read_to_string(path.join("stat"))
+        .ok()
+        .and_then(|x| parse_start_ticks(&x))?;
+    let boot_time = path
+        .parent()
+        .and_then(|x| read_to_string(x.join("stat")).ok())
+        .and_then(|x| parse_boot_time(&x))?;
+
+    let ticks_per_second = match unsafe { libc::sysconf(libc::_SC_CLK_TCK) } {
+        x if x > 0 => x as u64,
+        _ => return None,
+    };
+
+    let since_boot = Duration::from_millis(ticks * 1000 / ticks_per_second);
+
+    Some(SystemTime::UNIX_EPOCH + Duration::from_secs(boot_time) + since_boot)
+}
+
+/// Value of the `name` line in a `/proc/<pid>/status` file
+pub(crate) fn status_field<'a>(status: &'a str, name: &str) -> Option<&'a str> {
+    status.lines().find_map(|line| {
+        line.split_once(':')
+            .filter(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+    })
+}
+
+/// `NSpid` of a `/proc/<pid>/status` file, empty on kernels without it
+pub(crate) fn parse_nspid(status: &str) -> Vec<NonZeroU32> {
+    status_field(status, "NSpid")
+        .map(|x| {
+            x.split_whitespace()
+                .filter_map(|x| x.parse().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// `starttime` of a `/proc/<pid>/stat` file, in clock ticks since boot
+pub(crate) fn parse_start_ticks(stat: &str) -> Option<u64> {
+    // `comm` may itself contain spaces and parentheses, so count fields from the last `)`
+    let (_, fields) = stat.rsplit_once(')')?;
+
+    // `state` is field 3 and `starttime` is field 22
+    fields.split_whitespace().nth(22 - 3)?.parse().ok()
+}
+
+/// `btime` of `/proc/stat`, in seconds since the epoch
+pub(crate) fn parse_boot_time(stat: &str) -> Option<u64> {
+    stat.lines()
+        .find_map(|line| line.strip_prefix("btime "))
+        .and_then(|x| x.trim().parse().ok())
+}
+
+/// Inode of a namespace link target, e.g. `pid:[4026531836]`
+pub(crate) fn parse_namespace_inode(link: &str) -> Option<u64> {
+    link.split_once(":[")
+        .and_then(|(_, x)| x.strip_suffix(']'))
+        .and_then(|x| x.parse().ok())
+}
+
+/// Errors from a process exiting while its procfs entries are being read
+pub(crate) fn is_exited(e: &std::io::Error) -> bool {
+    matches!(e.raw_os_error(), Some(libc::ENOENT | libc::ESRCH))
+}
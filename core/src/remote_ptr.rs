@@ -1,14 +1,24 @@
-use crate::{error::Error, process::ProcessMemory};
+use crate::{error::Error, pod::Pod, process::ProcessMemory};
 use std::mem::MaybeUninit;
 
 #[repr(transparent)]
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct RemotePtr<T>(*const T);
+/// Null, without needing `T: Default` as the derive would
+impl<T> Default for RemotePtr<T> {
+    fn default() -> Self {
+        Self(std::ptr::null())
+    }
+}
 impl<T> RemotePtr<T> {
     pub fn new(ptr: *const T) -> Self {
         Self(ptr)
     }
 
+    pub fn addr(&self) -> usize {
+        self.0.addr()
+    }
+
     pub fn is_null(&self) -> bool {
         self.0.is_null()
     }
@@ -21,7 +31,10 @@ impl<T> RemotePtr<T> {
         RemotePtr(self.0.cast())
     }
 
-    pub unsafe fn read(&self, process: impl ProcessMemory) -> Result<T, Error> {
+    pub fn read(&self, process: impl ProcessMemory) -> Result<T, Error>
+    where
+        T: Pod,
+    {
         let mut output = MaybeUninit::uninit();
         unsafe {
             process.read_mem_into_unsafe(output.as_mut_ptr(), self.0.addr(), size_of::<T>())?;
@@ -37,11 +50,10 @@ impl<T> RemotePtr<T> {
         process.read_mem(self.0.addr(), length)
     }
 
-    pub unsafe fn read_array(
-        &self,
-        process: impl ProcessMemory,
-        length: usize,
-    ) -> Result<Vec<T>, Error> {
+    pub fn read_array(&self, process: impl ProcessMemory, length: usize) -> Result<Vec<T>, Error>
+    where
+        T: Pod,
+    {
         let mut buffer = Vec::new();
         buffer.resize_with(length, MaybeUninit::<T>::uninit);
 
@@ -0,0 +1,167 @@
+use std::ffi::{CStr, c_char, c_void};
+
+use crate::{
+    containers::CPtrList,
+    error::{Context, Error, ResultExt},
+    ids::IdsTables,
+    language::Language,
+    layout::Layout,
+    process::{GameProcess, ProcessMemory},
+    remote_ptr::RemotePtr,
+    snapshot::{Snapshot, View},
+};
+
+pub use macros::RemoteStruct;
+
+/// How a field of a [`RemoteStruct`] is decoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    /// Read as is
+    Value,
+    /// Integer decoded into an enum
+    Lookup,
+    /// Integer decoded with the IDS tables of the title
+    Ids,
+    /// Fixed size text, e.g. a res ref
+    Text,
+    /// Pointer to text
+    CString,
+    /// Embedded structure with this name
+    Nested(&'static str),
+    /// `CPtrList` of pointers to the structure with this name
+    List(&'static str),
+}
+
+/// A field of a [`RemoteStruct`], with its offset from the attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldInfo {
+    pub name: &'static str,
+    pub offset: isize,
+    /// Bytes taken up in the structure, 0 for lists
+    pub size: usize,
+    pub kind: FieldKind,
+}
+
+/// Everything decoding a [`RemoteStruct`] needs besides its bytes
+#[derive(Debug, Clone, Copy)]
+pub struct ReadContext<'a, P> {
+    pub process: P,
+    pub layout: &'a Layout,
+    pub ids: IdsTables,
+    pub language: Language,
+}
+impl<'a> ReadContext<'a, &'a GameProcess> {
+    pub fn new(process: &'a GameProcess) -> Self {
+        Self {
+            process,
+            layout: &process.layout,
+            ids: process.title.ids(),
+            language: process.language,
+        }
+    }
+}
+
+/// A game structure decoded field by field from a [`Snapshot`], with offsets from the [`Layout`].
+/// Implemented with `#[derive(RemoteStruct)]`, see the `macros` crate for its attributes
+pub trait RemoteStruct: Sized {
+    /// Name in the game, in errors and of its table in layout files
+    const NAME: &'static str;
+    /// Default `size` of the layout
+    const SIZE: usize;
+    const FIELDS: &'static [FieldInfo];
+
+    type Layout;
+
+    fn layout(layout: &Layout) -> &Self::Layout;
+    fn size(layout: &Self::Layout) -> usize;
+    /// Offset of `field` in this layout
+    fn offset_of(layout: &Self::Layout, field: &str) -> Option<isize>;
+
+    fn from_view<P: ProcessMemory + Copy>(ctx: &ReadContext<P>, view: View) -> Result<Self, Error>;
+
+    /// Reads the structure at `ptr` with a single snapshot
+    fn read<P: ProcessMemory + Copy>(
+        ctx: &ReadContext<P>,
+        ptr: RemotePtr<c_void>,
+    ) -> Result<Self, Error> {
+        let size = Self::size(Self::layout(ctx.layout));
+        let snapshot = Snapshot::read(ctx.process, Self::NAME, ptr, size)?;
+
+        Self::from_view(ctx, snapshot.view())
+    }
+}
+
+/// Text of a fixed size field, which is only NUL terminated when shorter than `size`
+pub(crate) fn read_text(
+    view: View,
+    language: Language,
+    field: &'static str,
+    offset: isize,
+    size: usize,
+) -> Result<String, Error> {
+    view.bytes(offset, size)
+        .map(|x| language.decode(x).into_owned())
+        .context(view.context(field, offset))
+}
+
+/// `None` if there's no NUL in the first `strlen` bytes
+pub(crate) fn read_c_string(
+    process: impl ProcessMemory,
+    language: Language,
+    char_ptr: RemotePtr<c_char>,
+    strlen: usize,
+) -> Result<Option<String>, Error> {
+    let bytes = unsafe { char_ptr.read_bytes(process, strlen) }.context(Context::Read {
+        structure: "string",
+        address: char_ptr.addr(),
+        size: strlen,
+    })?;
+
+    Ok(CStr::from_bytes_until_nul(&bytes)
+        .ok()
+        .map(|x| language.decode(x.to_bytes()).into_owned()))
+}
+
+/// Reads every element of the `CPtrList` at `offset` in `view`, with one batched read for all of
+/// them. Elements which can't be read are returned as errors instead of failing the whole list,
+/// as the game rewrites lists such as effects during area transitions
+pub(crate) fn read_list<T: RemoteStruct, P: ProcessMemory + Copy>(
+    ctx: &ReadContext<P>,
+    view: View,
+    field: &'static str,
+    offset: isize,
+) -> Result<(Vec<T>, Vec<Error>), Error> {
+    let layout = ctx.layout;
+    let size = T::size(T::layout(layout));
+    let mut errors = Vec::new();
+
+    let ptrs = CPtrList::<c_void>::from_view(&layout.ptr_list, view.field_at(field, offset)?)
+        .context(view.context(field, offset))?
+        .iter(ctx.process, &layout.ptr_list_node)
+        .filter_map(|x| x.map_err(|e| errors.push(e)).ok())
+        .collect::<Vec<_>>();
+
+    let snapshots = match Snapshot::read_many(ctx.process, T::NAME, &ptrs, size) {
+        Ok(x) => x.into_iter().map(Ok).collect(),
+        // One of the elements was freed, read them one at a time to keep the rest
+        Err(_) => ptrs
+            .iter()
+            .map(|x| Snapshot::read(ctx.process, T::NAME, *x, size))
+            .collect::<Vec<_>>(),
+    };
+
+    let mut items = Vec::with_capacity(snapshots.len());
+    for snapshot in snapshots {
+        match snapshot.and_then(|x| T::from_view(ctx, x.view())) {
+            Ok(x) => items.push(x),
+            Err(e) => errors.push(e),
+        }
+    }
+
+    let errors = errors
+        .into_iter()
+        .map(|e| e.context(view.context(field, offset)))
+        .collect();
+
+    Ok((items, errors))
+}
@@ -0,0 +1,222 @@
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{LazyLock, Mutex},
+};
+
+use crate::{
+    build_id::BuildId,
+    error::Error,
+    layout::GlobalsLayout,
+    process::{GameProcess, ProcessMemory},
+};
+
+/// Byte pattern with wildcards, written like `48 8D 05 ?? ?? ?? ??`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    bytes: Vec<Option<u8>>,
+}
+impl Signature {
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub fn matches(&self, bytes: &[u8]) -> bool {
+        bytes.len() >= self.len()
+            && self
+                .bytes
+                .iter()
+                .zip(bytes)
+                .all(|(pattern, byte)| pattern.is_none_or(|x| x == *byte))
+    }
+
+    /// Offset of the first match in `haystack`
+    pub fn find(&self, haystack: &[u8]) -> Option<usize> {
+        self.find_all(haystack).next()
+    }
+
+    pub fn find_all<'a>(&'a self, haystack: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+        let end = (haystack.len() + 1).saturating_sub(self.len());
+
+        (0..end).filter(move |&i| self.matches(&haystack[i..]))
+    }
+}
+impl FromStr for Signature {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = s
+            .split_whitespace()
+            .map(|x| match x {
+                "?" | "??" => Ok(None),
+                x if x.len() == 2 => {
+                    u8::from_str_radix(x, 16)
+                        .map(Some)
+                        .map_err(|e| Error::InvalidSignature {
+                            signature: s.into(),
+                            msg: format!("'{x}': {e}"),
+                        })
+                }
+                x => Err(Error::InvalidSignature {
+                    signature: s.into(),
+                    msg: format!("'{x}' is not a byte"),
+                }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if bytes.is_empty() {
+            return Err(Error::InvalidSignature {
+                signature: s.into(),
+                msg: "Empty signature".into(),
+            });
+        }
+
+        Ok(Self { bytes })
+    }
+}
+
+/// Computes the target of a rip-relative operand.
+///
+/// `instruction_end` is the address of the next instruction, as rip points there when the
+/// displacement is applied.
+pub fn resolve_rip_relative(instruction_end: usize, displacement: i32) -> usize {
+    instruction_end.wrapping_add_signed(displacement as isize)
+}
+
+/// Locates a global by an instruction which references it rip-relatively
+#[derive(Debug, Clone, Copy)]
+pub struct GlobalSignature {
+    pub name: &'static str,
+    pub pattern: &'static str,
+    /// Offset from the start of the match to the 32 bit displacement
+    pub displacement_offset: usize,
+    /// Offset from the start of the match to the end of the referencing instruction
+    pub instruction_end: usize,
+}
+
+/// `movzx ecx, cx; shl rcx, 4; lea rax, [entity_list]; mov rax, [rax+rcx+8]`, from the
+/// indexed lookup into the entity array in `CGameObjectArray::GetShare`
+pub const ENTITY_LIST: GlobalSignature = GlobalSignature {
+    name: "entity_list",
+    pattern: "0F B7 C9 48 C1 E1 04 48 8D 05 ?? ?? ?? ?? 48 8B 44 01 08",
+    displacement_offset: 10,
+    instruction_end: 14,
+};
+
+/// Executable code of the game, read once so several signatures can be scanned cheaply
+#[derive(Debug)]
+pub struct Scanner {
+    regions: Vec<(usize, Vec<u8>)>,
+}
+impl Scanner {
+    pub fn new(process: &GameProcess) -> Result<Self, Error> {
+        let maps = process.memory_map()?;
+
+        let regions = maps
+            .mappings_of(&process.exe)
+            .filter(|x| x.permissions.read && x.permissions.execute)
+            .map(|x| {
+                process
+                    .read_mem(x.range.start, x.len())
+                    .map(|mem| (x.range.start, mem))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(Self { regions })
+    }
+
+    pub fn from_regions(regions: Vec<(usize, Vec<u8>)>) -> Self {
+        Self { regions }
+    }
+
+    /// Absolute address of the first match
+    pub fn scan(&self, signature: &Signature) -> Option<usize> {
+        self.regions
+            .iter()
+            .find_map(|(start, mem)| signature.find(mem).map(|x| start + x))
+    }
+
+    /// Absolute address of the global referenced by `global`
+    pub fn locate(&self, global: &GlobalSignature) -> Result<usize, Error> {
+        let signature: Signature = global.pattern.parse()?;
+
+        self.regions
+            .iter()
+            .find_map(|(start, mem)| {
+                let found = signature.find(mem)?;
+
+                let displacement = mem
+                    .get(found + global.displacement_offset..)?
+                    .first_chunk::<4>()
+                    .copied()
+                    .map(i32::from_le_bytes)?;
+
+                Some(resolve_rip_relative(
+                    start + found + global.instruction_end,
+                    displacement,
+                ))
+            })
+            .ok_or(Error::SignatureNotFound { name: global.name })
+    }
+}
+
+/// Offsets of the game's globals, relative to the executable's load address
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Globals {
+    pub entity_list: usize,
+}
+impl Globals {
+    /// Scans the game for its globals, or returns the result of an earlier scan of the same build
+    pub fn locate(process: &GameProcess) -> Result<Globals, Error> {
+        static CACHE: LazyLock<Mutex<HashMap<BuildId, Globals>>> = LazyLock::new(Default::default);
+
+        if let Some(globals) = CACHE.lock().unwrap().get(&process.build_id) {
+            return Ok(*globals);
+        }
+
+        let scanner = Scanner::new(process)?;
+        let globals = Self::scan(
+            &scanner,
+            process.base_address.get(),
+            &process.layout.globals,
+        )?;
+
+        CACHE
+            .lock()
+            .unwrap()
+            .insert(process.build_id.clone(), globals);
+
+        Ok(globals)
+    }
+
+    /// Falls back to the offsets the layout knows for a build, `known`, when a signature is
+    /// missing. Fails without one, rather than reading the build at another build's offsets
+    pub fn scan(
+        scanner: &Scanner,
+        base_address: usize,
+        known: &GlobalsLayout,
+    ) -> Result<Globals, Error> {
+        let relative = |global: &GlobalSignature, known: Option<usize>| {
+            let address = match (scanner.locate(global), known) {
+                (Ok(address), _) => address,
+                (Err(Error::SignatureNotFound { .. }), Some(offset)) => return Ok(offset),
+                (Err(e), _) => return Err(e),
+            };
+
+            address.checked_sub(base_address).ok_or_else(|| {
+                Error::Memory(format!(
+                    "{} resolved to 0x{address:x}, below the executable at 0x{base_address:x}",
+                    global.name
+                ))
+            })
+        };
+
+        Ok(Globals {
+            entity_list: relative(&ENTITY_LIST, known.entity_list)?,
+        })
+    }
+}
@@ -0,0 +1,172 @@
+use std::ffi::c_void;
+
+use crate::{
+    error::{Context, Error, ResultExt},
+    pod::Pod,
+    process::{ProcessMemory, ReadRequest},
+    remote_ptr::RemotePtr,
+};
+
+/// Local copy of a remote structure, read with a single syscall so every field comes from the
+/// same moment in time
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    /// Name of the structure, for error context
+    structure: &'static str,
+    address: usize,
+    bytes: Vec<u8>,
+}
+impl Snapshot {
+    pub fn read(
+        process: impl ProcessMemory,
+        structure: &'static str,
+        ptr: RemotePtr<c_void>,
+        size: usize,
+    ) -> Result<Self, Error> {
+        let bytes = process.read_mem(ptr.addr(), size).context(Context::Read {
+            structure,
+            address: ptr.addr(),
+            size,
+        })?;
+
+        Ok(Self {
+            structure,
+            address: ptr.addr(),
+            bytes,
+        })
+    }
+
+    /// Reads a structure of `size` bytes at each of `ptrs` in one batch
+    pub fn read_many(
+        process: impl ProcessMemory,
+        structure: &'static str,
+        ptrs: &[RemotePtr<c_void>],
+        size: usize,
+    ) -> Result<Vec<Self>, Error> {
+        let mut snapshots = ptrs
+            .iter()
+            .map(|ptr| Self {
+                structure,
+                address: ptr.addr(),
+                bytes: vec![0; size],
+            })
+            .collect::<Vec<_>>();
+
+        let mut requests = snapshots
+            .iter_mut()
+            .map(|x| ReadRequest {
+                address: x.address,
+                buffer: &mut x.bytes,
+            })
+            .collect::<Vec<_>>();
+
+        process
+            .read_batch(&mut requests)
+            .context(Context::ReadMany {
+                structure,
+                count: ptrs.len(),
+            })?;
+
+        Ok(snapshots)
+    }
+
+    pub fn from_bytes(structure: &'static str, address: usize, bytes: Vec<u8>) -> Self {
+        Self {
+            structure,
+            address,
+            bytes,
+        }
+    }
+
+    pub fn view(&self) -> View<'_> {
+        View {
+            structure: self.structure,
+            address: self.address,
+            bytes: &self.bytes,
+        }
+    }
+}
+
+/// Bounds checked field access into a [`Snapshot`]
+#[derive(Debug, Clone, Copy)]
+pub struct View<'a> {
+    structure: &'static str,
+    address: usize,
+    bytes: &'a [u8],
+}
+impl<'a> View<'a> {
+    /// Same view, labelled as `structure` in errors
+    pub fn named(self, structure: &'static str) -> Self {
+        Self { structure, ..self }
+    }
+
+    pub fn structure(&self) -> &'static str {
+        self.structure
+    }
+
+    /// Remote address this view starts at
+    pub fn address(&self) -> usize {
+        self.address
+    }
+
+    pub fn ptr(&self) -> RemotePtr<c_void> {
+        RemotePtr::new(self.address as *const c_void)
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub fn bytes(&self, offset: isize, length: usize) -> Result<&'a [u8], Error> {
+        let out_of_bounds = || Error::SnapshotOutOfBounds {
+            offset,
+            length,
+            size: self.bytes.len(),
+        };
+
+        let start = usize::try_from(offset).map_err(|_| out_of_bounds())?;
+        let end = start.checked_add(length).ok_or_else(out_of_bounds)?;
+
+        self.bytes.get(start..end).ok_or_else(out_of_bounds)
+    }
+
+    /// View of a nested structure starting at `offset`
+    pub fn at(&self, offset: isize) -> Result<View<'a>, Error> {
+        let start = self.bytes(offset, 0)?;
+        let start = start.as_ptr() as usize - self.bytes.as_ptr() as usize;
+
+        Ok(View {
+            structure: self.structure,
+            address: self.address + start,
+            bytes: &self.bytes[start..],
+        })
+    }
+
+    pub fn get<T: Pod>(&self, offset: isize) -> Result<T, Error> {
+        let bytes = self.bytes(offset, size_of::<T>())?;
+        Ok(unsafe { bytes.as_ptr().cast::<T>().read_unaligned() })
+    }
+
+    /// [`Self::get`], naming `field` in any error
+    pub fn field<T: Pod>(&self, field: &'static str, offset: isize) -> Result<T, Error> {
+        self.get(offset).context(self.context(field, offset))
+    }
+
+    /// [`Self::at`], naming `field` in any error
+    pub fn field_at(&self, field: &'static str, offset: isize) -> Result<View<'a>, Error> {
+        self.at(offset).context(self.context(field, offset))
+    }
+
+    /// Context for errors decoding `field`, which is at `offset`
+    pub fn context(&self, field: &'static str, offset: isize) -> Context {
+        Context::Field {
+            structure: self.structure,
+            field,
+            address: self.address.wrapping_add_signed(offset),
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use super::{describe::effect, own_process};
+use crate::{
+    clock::{GameDuration, GameTime, Remaining},
+    ids::effect::Effect,
+    layout::ClockLayout,
+};
+
+#[test]
+fn computes_remaining_time() {
+    let now = GameTime(1000);
+    let mut stoneskin = effect(Effect::ProtectionStoneskin, 8, 0);
+    stoneskin.duration = 1000 + 42 * 15;
+
+    assert_eq!(
+        stoneskin.remaining(now),
+        Remaining::Timed(GameDuration::from_seconds(42))
+    );
+    assert_eq!(stoneskin.remaining(now).to_string(), "42s left");
+    assert_eq!(stoneskin.remaining(GameTime(2000)), Remaining::Expired);
+
+    stoneskin.duration_type = 3;
+    assert_eq!(stoneskin.remaining(now).to_string(), "starts in 42s");
+
+    stoneskin.duration_type = 1;
+    assert_eq!(stoneskin.remaining(now), Remaining::Permanent);
+    stoneskin.duration_type = 2;
+    assert_eq!(stoneskin.remaining(now), Remaining::WhileEquipped);
+}
+
+#[test]
+fn converts_durations() {
+    let duration = GameDuration(185 * 15 + 1);
+
+    assert_eq!(duration.seconds(), 186);
+    assert_eq!(duration.rounds(), 31);
+    assert_eq!(duration.to_string(), "3m 06s");
+    assert_eq!(GameDuration(0).rounds(), 0);
+}
+
+#[test]
+fn reads_game_time() {
+    let game_time: u32 = 4321;
+    let mut process = own_process();
+
+    assert_eq!(GameTime::read(&process).unwrap(), None);
+
+    let clock: ClockLayout = toml::from_str(&format!(
+        "game_time = \"{:#x} -> u32\"",
+        &game_time as *const u32 as usize
+    ))
+    .unwrap();
+    process.layout.clock = clock;
+
+    assert_eq!(GameTime::read(&process).unwrap(), Some(GameTime(4321)));
+    assert!(toml::from_str::<ClockLayout>("game_time = \"base -> [\"").is_err());
+}
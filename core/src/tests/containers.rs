@@ -0,0 +1,198 @@
+use std::{ffi::c_void, ptr::null};
+
+use super::own_process;
+use crate::{
+    build_id::BuildId,
+    containers::{CPtrList, CTypedPtrArray, RemoteArray},
+    error::Error,
+    layout::{Layout, LayoutDatabase},
+    remote_ptr::RemotePtr,
+    snapshot::Snapshot,
+    title::GameTitle,
+};
+
+/// `CPtrListNode` as laid out in the built in layout
+#[repr(C)]
+struct Node {
+    next: *const Node,
+    prev: *const Node,
+    data: *const c_void,
+}
+
+fn layout() -> Layout {
+    LayoutDatabase::builtin()
+        .and_then(|db| db.select(GameTitle::Bgee, &BuildId(vec![])).cloned())
+        .unwrap()
+}
+
+/// Nodes pointing to the next one, with the data of each being its index
+fn nodes(count: usize) -> Vec<Node> {
+    (0..count)
+        .map(|i| Node {
+            next: null(),
+            prev: null(),
+            data: i as *const c_void,
+        })
+        .collect()
+}
+
+fn link(nodes: &mut [Node]) {
+    for i in 1..nodes.len() {
+        let next = &nodes[i] as *const Node;
+        nodes[i - 1].next = next;
+    }
+}
+
+/// Snapshot of a `CPtrList` header starting at `head` with `count` nodes
+fn list(head: *const Node, count: u32) -> Snapshot {
+    let layout = layout();
+
+    let mut bytes = vec![0; 0x20];
+    let head_offset = layout.ptr_list.head as usize;
+    let count_offset = layout.ptr_list.count as usize;
+    bytes[head_offset..head_offset + 8].copy_from_slice(&(head as usize).to_ne_bytes());
+    bytes[count_offset..count_offset + 4].copy_from_slice(&count.to_ne_bytes());
+
+    Snapshot::from_bytes("CPtrList", 0x1000, bytes)
+}
+
+fn walk(snapshot: &Snapshot, limit: usize) -> (Vec<usize>, Option<Error>) {
+    let process = own_process();
+    let layout = layout();
+
+    let mut read = Vec::new();
+    let mut error = None;
+
+    let list = CPtrList::<c_void>::from_view(&layout.ptr_list, snapshot.view()).unwrap();
+    for x in list.iter(&process, &layout.ptr_list_node).with_limit(limit) {
+        match x {
+            Ok(x) => read.push(x.addr()),
+            Err(e) => error = Some(e),
+        }
+    }
+
+    (read, error)
+}
+
+#[test]
+fn walks_ptr_list() {
+    let mut nodes = nodes(3);
+    link(&mut nodes);
+
+    let (read, error) = walk(&list(nodes.as_ptr(), 3), 100);
+    assert_eq!(read, [0, 1, 2]);
+    assert!(error.is_none());
+
+    let (read, error) = walk(&list(null(), 0), 100);
+    assert!(read.is_empty() && error.is_none());
+}
+
+#[test]
+fn detects_cycles() {
+    let mut nodes = nodes(3);
+    link(&mut nodes);
+    nodes[2].next = &nodes[1];
+
+    let (read, error) = walk(&list(nodes.as_ptr(), 10), 100);
+    assert_eq!(read, [0, 1, 2]);
+    assert!(matches!(
+        error,
+        Some(Error::InvalidContainer {
+            structure: "CPtrList",
+            address: 0x1000,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn stops_at_short_list() {
+    let mut nodes = nodes(2);
+    link(&mut nodes);
+
+    let (read, error) = walk(&list(nodes.as_ptr(), 5), 100);
+    assert_eq!(read, [0, 1]);
+    assert_eq!(
+        error.unwrap().to_string(),
+        "CPtrList at 0x1000 ends after 2 of 5 nodes"
+    );
+}
+
+#[test]
+fn limits_list_length() {
+    let mut nodes = nodes(5);
+    link(&mut nodes);
+
+    let (read, error) = walk(&list(nodes.as_ptr(), 5), 3);
+    assert_eq!(read, [0, 1, 2]);
+    assert!(matches!(error, Some(Error::InvalidContainer { .. })));
+}
+
+#[test]
+fn reports_unreadable_nodes() {
+    let mut nodes = nodes(2);
+    link(&mut nodes);
+    nodes[1].next = 0x10 as *const Node;
+
+    let (read, error) = walk(&list(nodes.as_ptr(), 3), 100);
+    assert_eq!(read, [0, 1]);
+    assert!(matches!(error.unwrap().root(), Error::Io(_)));
+}
+
+#[test]
+fn reads_arrays_in_chunks() {
+    let process = own_process();
+
+    let values = (0..1000u32).collect::<Vec<_>>();
+    let array = RemoteArray::new("Test", RemotePtr::new(values.as_ptr()), values.len());
+
+    let read = array.iter(&process).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(read, values);
+
+    let limited = array.iter(&process).with_limit(300).collect::<Vec<_>>();
+    assert_eq!(limited.len(), 301);
+    assert!(limited[..300].iter().all(Result::is_ok));
+    assert!(matches!(limited[300], Err(Error::InvalidContainer { .. })));
+
+    let unreadable = RemoteArray::new("Test", RemotePtr::<u32>::new(0x10 as *const u32), 4);
+    let read = unreadable.iter(&process).collect::<Vec<_>>();
+    assert_eq!(read.len(), 1);
+    assert!(read[0].is_err());
+}
+
+#[test]
+fn reads_typed_ptr_arrays() {
+    let process = own_process();
+    let layout = layout().typed_ptr_array;
+
+    let values = [0x10usize, 0x20, 0x30];
+    let header = |size: i32| {
+        let mut bytes = vec![0; 0x20];
+        let data = layout.data as usize;
+        let size_offset = layout.size as usize;
+        bytes[data..data + 8].copy_from_slice(&(values.as_ptr() as usize).to_ne_bytes());
+        bytes[size_offset..size_offset + 4].copy_from_slice(&size.to_ne_bytes());
+
+        Snapshot::from_bytes("CTypedPtrArray", 0x2000, bytes)
+    };
+
+    let snapshot = header(3);
+    let array = CTypedPtrArray::<c_void>::from_view(&layout, snapshot.view()).unwrap();
+    assert_eq!(array.len(), 3);
+
+    let read = array
+        .iter(&process)
+        .map(|x| x.map(|x| x.addr()))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(read, values);
+
+    let snapshot = header(-1);
+    assert!(matches!(
+        CTypedPtrArray::<c_void>::from_view(&layout, snapshot.view()),
+        Err(Error::InvalidContainer {
+            structure: "CTypedPtrArray",
+            ..
+        })
+    ));
+}
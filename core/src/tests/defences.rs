@@ -0,0 +1,122 @@
+use super::{decode_zeroed, describe::effect, own_process};
+use crate::{
+    defences::{Defences, LevelPool},
+    ids::{effect::Effect, school::School, state::State},
+    remote_struct::{ReadContext, RemoteStruct},
+    types::{CDerivedStats, Lookup},
+};
+
+fn stats(fields: &[(&str, u32)]) -> CDerivedStats {
+    let process = own_process();
+    let ctx = ReadContext::new(&process);
+
+    decode_zeroed(&ctx, |layout, bytes| {
+        for (name, value) in fields {
+            let offset = CDerivedStats::offset_of(layout, name).unwrap() as usize;
+            let size = CDerivedStats::FIELDS
+                .iter()
+                .find(|x| x.name == *name)
+                .unwrap()
+                .size;
+            bytes[offset..offset + size].copy_from_slice(&value.to_ne_bytes()[..size]);
+        }
+    })
+}
+
+#[test]
+fn collects_spell_protections() {
+    let effects = [
+        effect(Effect::SpellImmunityByPowerLevel, 1, 0),
+        effect(Effect::SpellImmunityByPowerLevel, 2, 0),
+        effect(Effect::SpellImmunityByPowerLevel, 3, 0),
+        effect(Effect::SpellBounceByPowerLevelDecrementing, 10, 7),
+        effect(
+            Effect::SpellProtectionBySchool,
+            0,
+            School::Necromancy as i32,
+        ),
+        effect(Effect::ProtectionFromOpcode, 0, 5),
+        effect(Effect::StateHaste, 0, 0),
+    ];
+    let defences = Defences::new(&stats(&[]), &effects);
+
+    assert_eq!(defences.spell_levels, [1, 2, 3].into());
+    assert_eq!(
+        defences.spell_turning,
+        [LevelPool {
+            levels: 10,
+            max_level: 7
+        }]
+    );
+    assert_eq!(defences.schools, [Lookup::Found(School::Necromancy)].into());
+    assert_eq!(defences.opcodes, [Lookup::Found(Effect::Charm)].into());
+
+    assert_eq!(
+        defences.summary(),
+        [
+            "Immune to level 1-3 spells",
+            "Reflects 10 spell levels of level 7 or lower",
+            "Immune to Necromancy spells",
+            "Immune to Charm",
+        ]
+    );
+}
+
+#[test]
+fn prefers_stats_over_effects() {
+    let effects = [
+        effect(Effect::ProtectionFromWeapons, 1, 0),
+        effect(Effect::ProtectionFromWeapons, 3, 0),
+        effect(Effect::ProtectionStoneskin, 8, 0),
+        effect(Effect::SpellEffectMirrorImage, 4, 0),
+    ];
+
+    let defences = Defences::new(&stats(&[("stone_skins", 5)]), &effects);
+    assert_eq!(defences.weapon_enchantment, Some(3));
+    assert_eq!(defences.stoneskins, 5);
+    assert_eq!(defences.mirror_images, 4);
+
+    let defences = Defences::new(&stats(&[]), &effects);
+    assert_eq!(defences.stoneskins, 8);
+}
+
+#[test]
+fn reads_stats_and_states() {
+    let stats = stats(&[
+        ("general_state", State::IMPROVED_INVISIBILITY.bits()),
+        ("resist_fire", 50),
+        ("resist_magic", 75),
+        ("see_invisible", 1),
+    ]);
+    let defences = Defences::new(&stats, []);
+
+    assert_eq!(defences.resistances.fire, 50);
+    assert!(defences.invisible && defences.improved_invisibility);
+    assert_eq!(
+        defences.summary(),
+        [
+            "75% magic resistance",
+            "Improved invisibility",
+            "True sight"
+        ]
+    );
+}
+
+#[test]
+fn names_weapon_and_spell_types() {
+    let effects = [
+        effect(Effect::ProtectionFromWeapons, 0, 3),
+        effect(Effect::ProtectionFromWeapons, 0, 99),
+        effect(Effect::SpellProtectionBySchool, 0, 99),
+    ];
+    let defences = Defences::new(&stats(&[]), &effects);
+
+    assert_eq!(
+        defences.summary(),
+        [
+            "Immune to school 99 spells",
+            "Immune to silver weapons",
+            "Immune to weapon type 99",
+        ]
+    );
+}
@@ -0,0 +1,160 @@
+use crate::{
+    clock::GameTime,
+    describe::describe_effects,
+    ids::{effect::Effect, school::School},
+    types::{CGameEffect, Lookup},
+};
+
+pub(super) fn effect(effect: Effect, parameter1: i32, parameter2: i32) -> CGameEffect {
+    CGameEffect {
+        version: "EFF V2.0".into(),
+        effect_id: Lookup::Found(effect),
+        target: 1,
+        spell_level: 0,
+        parameter1,
+        parameter2,
+        duration_type: 0,
+        duration: 0,
+        probability1: 100,
+        probability2: 0,
+        res: String::new(),
+        dice_thrown: 0,
+        dice_sides: 0,
+        saving_throw: 0,
+        save_bonus: 0,
+        special: 0,
+        school: Lookup::Unknown(0),
+        min_level: 0,
+        max_level: 0,
+        resistance: 0,
+        parameter3: 0,
+        parameter4: 0,
+        res_2: String::new(),
+        res_3: String::new(),
+        source_res: String::new(),
+        script_name: String::new(),
+        caster_level: 0,
+        secondary_type: Lookup::Unknown(0),
+    }
+}
+
+#[test]
+fn describes_stat_modifiers() {
+    assert_eq!(
+        effect(Effect::StatACVsDamageTypeModifier, 2, 0x8).describe(),
+        "+2 AC vs. slashing"
+    );
+    assert_eq!(
+        effect(Effect::StatACVsDamageTypeModifier, 3, 0x10).describe(),
+        "Base AC set to 3"
+    );
+    assert_eq!(
+        effect(Effect::StatStrengthModifier, -1, 0).describe(),
+        "-1 Strength"
+    );
+    assert_eq!(
+        effect(Effect::StatStrengthModifier, 18, 1).describe(),
+        "Strength set to 18"
+    );
+    assert_eq!(
+        effect(Effect::StatFireResistanceModifier, 50, 0).describe(),
+        "+50% fire resistance"
+    );
+    assert_eq!(
+        effect(Effect::StatMovementModifier, 50, 2).describe(),
+        "movement rate at 50% of normal"
+    );
+}
+
+#[test]
+fn describes_protections() {
+    assert_eq!(
+        effect(Effect::ProtectionFromWeapons, 2, 0).describe(),
+        "Immune to weapons below +2"
+    );
+    assert_eq!(
+        effect(Effect::ProtectionFromOpcode, 0, 5).describe(),
+        "Immune to Charm"
+    );
+    assert_eq!(
+        effect(
+            Effect::SpellProtectionBySchool,
+            0,
+            School::Necromancy as i32
+        )
+        .describe(),
+        "Immune to Necromancy spells"
+    );
+    assert_eq!(
+        effect(Effect::ProtectionStoneskin, 8, 0).describe(),
+        "8 stoneskins"
+    );
+}
+
+#[test]
+fn describes_damage_with_dice() {
+    let mut fireball = effect(Effect::HPDamage, 0, 0x80000);
+    fireball.dice_thrown = 6;
+    fireball.dice_sides = 6;
+
+    assert_eq!(fireball.describe(), "6d6 fire damage");
+}
+
+#[test]
+fn falls_back_to_names() {
+    assert_eq!(
+        effect(Effect::SpellEffectFarsight, 0, 0).describe(),
+        "SpellEffectFarsight"
+    );
+
+    let mut unknown = effect(Effect::Crash, 0, 0);
+    unknown.effect_id = Lookup::Unknown(999);
+    assert_eq!(unknown.describe(), "Opcode 999");
+}
+
+#[test]
+fn merges_spell_level_immunities() {
+    // Globe of Invulnerability, applied one level at a time
+    let effects = [4, 2, 1, 3, 6]
+        .map(|level| effect(Effect::SpellImmunityByPowerLevel, level, 0))
+        .into_iter()
+        .chain([effect(Effect::StateHaste, 0, 0)])
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        describe_effects(&effects, None),
+        [
+            "Immune to level 1-4 spells",
+            "Immune to level 6 spells",
+            "Hasted"
+        ]
+    );
+}
+
+#[test]
+fn merges_levels_lasting_as_long() {
+    let timed = |effect_id, level, duration| {
+        let mut effect = effect(effect_id, level, 0);
+        effect.duration = duration;
+        effect
+    };
+    let now = GameTime(1000);
+
+    // Globe of Invulnerability, and Minor Globe cast later
+    let effects = [
+        timed(Effect::SpellImmunityByPowerLevel, 1, 1600),
+        timed(Effect::SpellImmunityByPowerLevel, 2, 1600),
+        timed(Effect::SpellImmunityByPowerLevel, 3, 1600),
+        timed(Effect::SpellImmunityByPowerLevel, 1, 1900),
+        timed(Effect::StateHaste, 0, 1150),
+    ];
+
+    assert_eq!(
+        describe_effects(&effects, Some(now)),
+        [
+            "Immune to level 1-3 spells: 40s left",
+            "Immune to level 1 spells: 1m 00s left",
+            "Hasted: 10s left"
+        ]
+    );
+}
@@ -0,0 +1,56 @@
+use crate::ids::{
+    alignment::{Alignment, AlignmentMask, GoodEvil, LawChaos},
+    enemy_ally::{EnemyAlly, Hostility},
+};
+
+#[test]
+fn classifies_hostility_by_selection_circle() {
+    assert_eq!(EnemyAlly::Pc.hostility(), Hostility::Friendly);
+    assert_eq!(EnemyAlly::Charmed.hostility(), Hostility::Friendly);
+    assert_eq!(EnemyAlly::GoodButRed.hostility(), Hostility::Hostile);
+    assert_eq!(EnemyAlly::GoodButBlue.hostility(), Hostility::Neutral);
+    assert_eq!(EnemyAlly::Neutral.hostility(), Hostility::Neutral);
+    assert_eq!(EnemyAlly::EvilButGreen.hostility(), Hostility::Friendly);
+    assert_eq!(EnemyAlly::EvilButBlue.hostility(), Hostility::Neutral);
+    assert_eq!(EnemyAlly::CharmedPc.hostility(), Hostility::Hostile);
+    assert_eq!(EnemyAlly::Enemy.hostility(), Hostility::Hostile);
+    assert_eq!(EnemyAlly::Inanimate.hostility(), Hostility::Inanimate);
+    assert_eq!(EnemyAlly::AreaObject.hostility(), Hostility::Inanimate);
+
+    // Outside of EA.IDS
+    assert_eq!(Hostility::of(15), Hostility::Friendly);
+    assert_eq!(Hostility::of(150), Hostility::Neutral);
+    assert_eq!(Hostility::of(230), Hostility::Hostile);
+}
+
+#[test]
+fn splits_alignment_into_axes() {
+    let alignment = Alignment::new(LawChaos::Chaotic, GoodEvil::Evil);
+
+    assert_eq!(alignment, Alignment::ChaoticEvil);
+    assert_eq!(alignment.law_chaos(), Some(LawChaos::Chaotic));
+    assert_eq!(alignment.good_evil(), Some(GoodEvil::Evil));
+    assert_eq!(Alignment::None.law_chaos(), None);
+
+    // Masks are no longer alignments
+    assert!(Alignment::try_from(0x03).is_err());
+}
+
+#[test]
+fn matches_alignment_masks() {
+    let evil = AlignmentMask::try_from(0x03).unwrap();
+
+    assert_eq!(evil, AlignmentMask::either_law_chaos(GoodEvil::Evil));
+    assert!(evil.matches(Alignment::LawfulEvil));
+    assert!(evil.matches(Alignment::NeutralEvil));
+    assert!(!evil.matches(Alignment::ChaoticNeutral));
+
+    let lawful_good = AlignmentMask::from(Alignment::LawfulGood);
+    assert_eq!(u8::from(lawful_good), 0x11);
+    assert!(lawful_good.matches(Alignment::LawfulGood));
+    assert!(!lawful_good.matches(Alignment::LawfulNeutral));
+
+    assert!(AlignmentMask::default().matches(Alignment::Neutral));
+    assert!(AlignmentMask::try_from(0x04).is_err());
+    assert!(AlignmentMask::try_from(0x40).is_err());
+}
@@ -0,0 +1,70 @@
+use std::{
+    fs::{create_dir_all, remove_dir_all},
+    os::unix::fs::symlink,
+};
+
+use crate::{error::Error, language::Language};
+
+#[test]
+fn decodes_utf8_first() {
+    let name = "Źdźbło Żółwia";
+
+    assert_eq!(Language::English.decode(name.as_bytes()), name);
+    assert_eq!(
+        Language::Russian.decode("Минск\0garbage".as_bytes()),
+        "Минск"
+    );
+}
+
+#[test]
+fn falls_back_to_legacy_encoding() {
+    // "Łódź" in Windows-1250
+    assert_eq!(Language::Polish.decode(b"\xA3\xF3d\x9F"), "Łódź");
+    // "Минск" in Windows-1251
+    assert_eq!(
+        Language::Russian.decode(b"\xCC\xE8\xED\xF1\xEA\0\xFF"),
+        "Минск"
+    );
+    // "剣" in Shift-JIS and "中文" in GBK
+    assert_eq!(Language::Japanese.decode(b"\x8C\x95"), "剣");
+    assert_eq!(Language::Chinese.decode(b"\xD6\xD0\xCE\xC4"), "中文");
+}
+
+#[test]
+fn replaces_invalid_bytes() {
+    // A lone Shift-JIS lead byte is invalid in UTF-8 too
+    assert_eq!(Language::Japanese.decode(b"a\x81"), "a\u{FFFD}");
+    assert_eq!(Language::English.decode(b""), "");
+}
+
+#[test]
+fn parses_locales() {
+    assert_eq!("pl_PL".parse::<Language>().unwrap(), Language::Polish);
+    assert_eq!("RU_ru".parse::<Language>().unwrap(), Language::Russian);
+    assert_eq!(Language::Chinese.to_string(), "zh_CN");
+    assert!(matches!(
+        "xx_XX".parse::<Language>(),
+        Err(Error::InvalidEnumValue {
+            enum_type: "Language",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn detects_language_from_open_files() {
+    let root = std::env::temp_dir().join(format!("bg-radar-language-{}", std::process::id()));
+    let _ = remove_dir_all(&root);
+    let fd = root.join("fd");
+    create_dir_all(&fd).unwrap();
+
+    assert_eq!(Language::detect(&root), None);
+
+    symlink("/dev/null", fd.join("0")).unwrap();
+    symlink("/games/BGEE/lang/pl_PL/dialog.tlk", fd.join("7")).unwrap();
+    assert_eq!(Language::detect(&root), Some(Language::Polish));
+
+    assert_eq!(Language::detect(&root.join("missing")), None);
+
+    let _ = remove_dir_all(&root);
+}
@@ -0,0 +1,81 @@
+use crate::{
+    build_id::BuildId,
+    error::Error,
+    layout::{Layout, LayoutDatabase},
+    title::GameTitle,
+};
+
+const BGEE_LAYOUT: &str = include_str!("../../layouts/bgee.toml");
+
+fn layout_for_build(build_id: &str, res_ref: isize) -> Layout {
+    let toml = BGEE_LAYOUT
+        .replace("build_ids = []", &format!("build_ids = [\"{build_id}\"]"))
+        .replace("res_ref = 0x540", &format!("res_ref = {res_ref}"));
+
+    Layout::parse("test", &toml).unwrap()
+}
+
+#[test]
+fn builtin_layouts_parse() {
+    let db = LayoutDatabase::builtin().unwrap();
+
+    let layout = db.select(GameTitle::Bg2ee, &BuildId(vec![0xAB])).unwrap();
+    assert_eq!(layout.sprite.res_ref, 0x540);
+    assert_eq!(layout.effect.base, 0x8);
+}
+
+#[test]
+fn prefers_exact_build() {
+    let mut db = LayoutDatabase::builtin().unwrap();
+    db.insert(layout_for_build("abcd", 0x123));
+
+    let exact = db
+        .select(GameTitle::Bgee, &BuildId(vec![0xAB, 0xCD]))
+        .unwrap();
+    assert_eq!(exact.sprite.res_ref, 0x123);
+
+    let generic = db.select(GameTitle::Bgee, &BuildId(vec![0x12])).unwrap();
+    assert_eq!(generic.sprite.res_ref, 0x540);
+}
+
+#[test]
+fn missing_title() {
+    let db = LayoutDatabase::builtin().unwrap();
+
+    assert!(matches!(
+        db.select(GameTitle::Pstee, &BuildId(vec![])),
+        Err(Error::MissingLayout { .. })
+    ));
+}
+
+#[test]
+fn rejects_incomplete_layouts() {
+    let toml = BGEE_LAYOUT.replace("head = 0x8\n", "");
+    assert!(matches!(
+        Layout::parse("test", &toml),
+        Err(Error::InvalidLayout { .. })
+    ));
+
+    let toml = BGEE_LAYOUT.replace("res_ref = 0x540", "res_ref = 0x540\nres_reff = 0x540");
+    assert!(matches!(
+        Layout::parse("test", &toml),
+        Err(Error::InvalidLayout { .. })
+    ));
+}
+
+#[test]
+fn loads_shared_layouts_once() {
+    let db = LayoutDatabase::shared().unwrap();
+
+    assert!(std::ptr::eq(db, LayoutDatabase::shared().unwrap()));
+}
+
+#[test]
+fn reports_unreadable_layout_dir() {
+    let mut db = LayoutDatabase::default();
+
+    assert!(matches!(
+        db.load_dir(std::path::Path::new("/nonexistent/bg-radar-layouts")),
+        Err(Error::InvalidLayout { .. })
+    ));
+}
@@ -0,0 +1,112 @@
+use std::path::Path;
+
+use super::{EXE_PATH, MEMORY_MAP};
+use crate::{
+    entity_list,
+    memory_map::{MemoryMap, MemoryMapping, Permissions},
+};
+
+fn memory_map() -> MemoryMap {
+    MEMORY_MAP.parse().expect("Failed to parse memory map")
+}
+
+#[test]
+fn parses_every_line() {
+    let maps = memory_map();
+
+    assert_eq!(maps.mappings().len(), MEMORY_MAP.trim_end().lines().count());
+}
+
+#[test]
+fn parses_path_with_spaces() {
+    let line = "568c2d000000-568c2da5d000 r-xp 00000000 00:1a 46734068                   /home/charlotte/.local/share/Steam/steamapps/common/Baldur's Gate Enhanced Edition/BaldursGate";
+
+    let mapping: MemoryMapping = line.parse().unwrap();
+
+    assert_eq!(
+        mapping,
+        MemoryMapping {
+            range: 0x568c2d000000..0x568c2da5d000,
+            permissions: Permissions {
+                read: true,
+                write: false,
+                execute: true,
+                shared: false,
+            },
+            offset: 0,
+            device: (0, 0x1a),
+            inode: 46734068,
+            pathname: Some(EXE_PATH.into()),
+        }
+    );
+}
+
+#[test]
+fn parses_anonymous_mapping() {
+    let mapping: MemoryMapping = "75a134000000-75a134021000 rw-p 00000000 00:00 0 "
+        .parse()
+        .unwrap();
+
+    assert_eq!(mapping.pathname, None);
+    assert_eq!(mapping.permissions.to_string(), "rw-p");
+}
+
+#[test]
+fn rejects_malformed_lines() {
+    let bad = [
+        "",
+        "568c2d000000 r-xp 00000000 00:1a 0",
+        "568c2d000000-568c2da5d000 rwzp 00000000 00:1a 0",
+        "568c2d000000-568c2da5d000 r-xp 00000000 001a 0",
+        "568c2da5d000-568c2d000000 r-xp 00000000 00:1a 0",
+        "568c2d000000-568c2da5d000 r-xp 00000000 00:1a",
+    ];
+
+    for line in bad {
+        assert!(line.parse::<MemoryMapping>().is_err(), "{line:?} parsed");
+    }
+
+    assert!(
+        "568c2d000000-568c2da5d000 r-xp 00000000 00:1a 0\nnonsense"
+            .parse::<MemoryMap>()
+            .is_err()
+    );
+}
+
+#[test]
+fn first_mapping_of_executable() {
+    let maps = memory_map();
+
+    let exe = maps.first_mapping_of(Path::new(EXE_PATH)).unwrap();
+
+    assert_eq!(exe.range.start, 0x568c2d000000);
+    assert!(exe.permissions.execute);
+    assert_eq!(maps.mappings_of(Path::new(EXE_PATH)).count(), 3);
+}
+
+#[test]
+fn containing_address() {
+    let maps = memory_map();
+    let exe = maps.first_mapping_of(Path::new(EXE_PATH)).unwrap();
+
+    let bss = maps
+        .containing(exe.range.start + entity_list::OFFSET)
+        .unwrap();
+    assert_eq!(bss.range, 0x568c2dc98000..0x568c30ad2000);
+    assert_eq!(bss.pathname, None);
+
+    // Gap between the text and rodata segments
+    assert_eq!(maps.containing(0x568c2da5d000), None);
+    assert_eq!(maps.containing(0), None);
+}
+
+#[test]
+fn writable_regions() {
+    let maps = memory_map();
+
+    assert!(maps.writable().all(|x| x.permissions.write));
+    assert!(
+        maps.writable()
+            .any(|x| x.pathname.as_deref() == Some("[heap]"))
+    );
+}
@@ -1,7 +1,44 @@
+mod clock;
+mod containers;
+mod defences;
+mod describe;
+mod ids;
+mod language;
+mod layout;
+mod memory_map;
+mod object_spec;
+mod permissions;
+mod pointer_path;
+mod process;
+mod procfs;
+mod remote_struct;
+mod signature;
+mod snapshot;
+mod title;
+mod types;
+mod watcher;
+#[cfg(feature = "write")]
+mod write;
+
 use std::{ffi::c_void, fs::File, io::Read, num::NonZero, path::Path};
 
 use crate::{
-    entity_list, error::Error, ids::classes::Class, process::{GameProcess, ProcessMemory}, remote_ptr::RemotePtr, types::{CGameAIBase, CGameObject, CGameSprite, ObjectType}, EntityPtr
+    EntityPtr,
+    build_id::BuildId,
+    entity_list,
+    error::Error,
+    language::Language,
+    layout::LayoutDatabase,
+    memory_map::MemoryMap,
+    pointer_path::PathValue,
+    process::{GameProcess, MemoryBackend, ProcessMemory},
+    procfs::PidNamespace,
+    remote_ptr::RemotePtr,
+    remote_struct::{ReadContext, RemoteStruct},
+    signature::{Globals, Scanner},
+    snapshot::Snapshot,
+    title::GameTitle,
+    types::{CGameAIBase, CGameSprite},
 };
 
 #[derive(Debug)]
@@ -66,7 +103,7 @@ impl MemoryRegion {
 struct MockProcess {
     process: GameProcess,
     memory_regions: Vec<MemoryRegion>,
-    maps: &'static str,
+    maps: MemoryMap,
 }
 impl ProcessMemory for &MockProcess {
     fn read_mem(&self, address: usize, length: usize) -> Result<Vec<u8>, Error> {
@@ -109,25 +146,78 @@ impl ProcessMemory for &MockProcess {
 
 const BASE_DIR: &str = env!("CARGO_MANIFEST_DIR");
 const MEMORY_MAP: &str = include_str!("../../dumps/bgee-memmap");
+const EXE_PATH: &str = "/home/charlotte/.local/share/Steam/steamapps/common/Baldur's Gate Enhanced Edition/BaldursGate";
 
 fn get_mock_process() -> MockProcess {
     let base_path = Path::new(BASE_DIR);
 
     let dump_file_path = base_path.join("dumps");
+    let maps: MemoryMap = MEMORY_MAP.parse().expect("Failed to parse memory map");
+    let build_id = BuildId::from_contents(MEMORY_MAP.as_bytes());
+    let layout = LayoutDatabase::builtin()
+        .and_then(|db| db.select(GameTitle::Bgee, &build_id).cloned())
+        .expect("Failed to load layout");
 
     MockProcess {
         process: GameProcess {
             path: dump_file_path.clone(),
             pid: NonZero::new(1).unwrap(),
-            base_address: crate::process::get_base_address_from_memory_map(MEMORY_MAP)
-                .expect("Failed to read memory map"),
+            base_address: crate::process::get_base_address_from_memory_map(
+                &maps,
+                Path::new(EXE_PATH),
+            )
+            .expect("Failed to read memory map"),
             name: "Mock Process".to_string(),
+            title: GameTitle::Bgee,
+            language: Language::default(),
+            exe: EXE_PATH.into(),
+            build_id,
+            layout,
+            backend: MemoryBackend::ProcessVmReadv,
+            namespace: PidNamespace::default(),
         },
         memory_regions: MemoryRegion::load_all(&dump_file_path),
-        maps: MEMORY_MAP,
+        maps,
     }
 }
 
+/// The test binary itself, for exercising the real `process_vm_readv` paths
+fn own_process() -> GameProcess {
+    let build_id = BuildId(vec![]);
+    let layout = LayoutDatabase::builtin()
+        .and_then(|db| db.select(GameTitle::Bgee, &build_id).cloned())
+        .expect("Failed to load layout");
+
+    GameProcess {
+        path: "/proc/self".into(),
+        pid: NonZero::new(std::process::id()).unwrap(),
+        base_address: NonZero::new(1).unwrap(),
+        name: "Test Process".to_string(),
+        title: GameTitle::Bgee,
+        language: Language::default(),
+        exe: std::env::current_exe().unwrap(),
+        build_id,
+        layout,
+        backend: MemoryBackend::ProcessVmReadv,
+        namespace: PidNamespace::default(),
+    }
+}
+
+/// `T` decoded from zeroed bytes of its layout's size, after `write` has filled in the fields a
+/// test is about
+fn decode_zeroed<T: RemoteStruct>(
+    ctx: &ReadContext<&GameProcess>,
+    write: impl FnOnce(&T::Layout, &mut [u8]),
+) -> T {
+    let layout = T::layout(ctx.layout);
+
+    let mut bytes = vec![0; T::size(layout)];
+    write(layout, &mut bytes);
+
+    let snapshot = Snapshot::from_bytes(T::NAME, 0x1000, bytes);
+    T::from_view(ctx, snapshot.view()).unwrap()
+}
+
 #[test]
 fn read_mem_test() -> Result<(), Error> {
     let process = get_mock_process();
@@ -149,20 +239,84 @@ fn read_mem_test() -> Result<(), Error> {
         });
     }
 
+    let entity_region = process
+        .maps
+        .containing(process.process.base_address.get() + entity_list::OFFSET)
+        .expect("Entity list is not mapped");
+    assert!(entity_region.permissions.write);
+
     let ai = entities
         .into_iter()
         .filter(|e| e.is_valid())
         .map(|e| {
-            let base = CGameAIBase::new(&process, &e);
+            let base = CGameAIBase::new(
+                &process,
+                &process.process.layout,
+                process.process.title.ids(),
+                process.process.language,
+                &e,
+            );
             base.map(|base| (e, base))
         })
         .filter_map(|base| match base {
-            Ok((e, Some(base))) if base.object.object_type == ObjectType::Sprite => {
-                Some(CGameSprite::new(&process, &e, base))
-            }
+            Ok((e, Some(base))) if base.object.is_sprite() => Some(CGameSprite::new(
+                &process,
+                &process.process.layout,
+                process.process.title.ids(),
+                process.process.language,
+                &e,
+                base,
+            )),
             _ => None,
         })
         .collect::<Result<Vec<_>, Error>>()?;
 
     panic!("{ai:#?}");
 }
+
+#[test]
+fn locate_entity_list_test() {
+    let process = get_mock_process();
+
+    let regions = process
+        .maps
+        .mappings_of(Path::new(EXE_PATH))
+        .filter(|x| x.permissions.read && x.permissions.execute)
+        .filter_map(|x| {
+            // Only dumps taken since the dump script saves code have it, the layout's offset is
+            // used without
+            let mem = (&process).read_mem(x.range.start, x.len()).ok()?;
+            Some((x.range.start, mem))
+        })
+        .collect();
+    let scanner = Scanner::from_regions(regions);
+
+    let globals = Globals::scan(
+        &scanner,
+        process.process.base_address.get(),
+        &process.process.layout.globals,
+    )
+    .unwrap();
+    assert_eq!(globals.entity_list, entity_list::OFFSET);
+    assert_eq!(globals.entity_list, 0xCBF780);
+}
+
+#[test]
+#[ignore = "the game time isn't located yet, set clock.game_time in bgee.toml first"]
+fn read_game_time_test() {
+    let process = get_mock_process();
+
+    let path = process
+        .process
+        .layout
+        .clock
+        .game_time
+        .as_ref()
+        .expect("No clock.game_time in the layout");
+    let value = path
+        .eval(&process, process.process.base_address.get())
+        .unwrap();
+
+    // The dumps are of a save some way into the game
+    assert!(matches!(value, PathValue::Unsigned(x) if x > 0), "{value}");
+}
@@ -0,0 +1,128 @@
+use crate::{
+    ids::{
+        alignment::Alignment, classes::Class, enemy_ally::EnemyAlly, gender::Gender,
+        general::General, race::Race,
+    },
+    object_spec::{ObjectIds, ObjectSpec},
+    types::{CAIObjectType, Lookup},
+};
+
+fn object(enemy_ally: EnemyAlly, class: Class, alignment: Alignment) -> CAIObjectType {
+    CAIObjectType {
+        name: Some("Xzar".into()),
+        enemy_ally: Lookup::Found(enemy_ally),
+        general: Lookup::Found(General::Humanoid),
+        race: Lookup::Found(Race::Human),
+        class: Lookup::Found(class),
+        instance: 0,
+        special_case: [0; 5],
+        specifics: 0,
+        gender: Lookup::Found(Gender::Male),
+        alignment: Lookup::Found(alignment),
+    }
+}
+
+fn spec(s: &str) -> ObjectSpec {
+    s.parse().unwrap()
+}
+
+#[test]
+fn parses_specifiers() {
+    assert_eq!(
+        spec("[ENEMY.HUMANOID.0.MAGE_ALL]"),
+        ObjectSpec::Ids(ObjectIds {
+            enemy_ally: 255,
+            general: 1,
+            class: 202,
+            ..Default::default()
+        })
+    );
+    assert_eq!(
+        spec("[pc.0.half_elf.0.0.female.mask_evil]"),
+        ObjectSpec::Ids(ObjectIds {
+            enemy_ally: 2,
+            race: 3,
+            gender: 2,
+            alignment: 0x03,
+            ..Default::default()
+        })
+    );
+    assert_eq!(spec("\"Imoen\""), ObjectSpec::Name("Imoen".into()));
+
+    assert!("ENEMY".parse::<ObjectSpec>().is_err());
+    assert!("[ENEMY..MAGE]".parse::<ObjectSpec>().is_err());
+    assert!("[FRIEND]".parse::<ObjectSpec>().is_err());
+    assert!("[0.0.0.0.0.0.0.0]".parse::<ObjectSpec>().is_err());
+}
+
+#[test]
+fn displays_ids_names() {
+    for s in [
+        "[ENEMY.HUMANOID.0.MAGE_ALL]",
+        "[GOODCUTOFF]",
+        "[0.0.0.0.0.0.MASK_GENEUTRAL]",
+        "[100.0.0.0.42]",
+        "\"Imoen\"",
+    ] {
+        assert_eq!(spec(s).to_string(), s);
+    }
+}
+
+#[test]
+fn matches_group_classes() {
+    let mage = spec("[0.0.0.MAGE_ALL]");
+
+    assert!(mage.matches(&object(
+        EnemyAlly::Enemy,
+        Class::FighterMage,
+        Alignment::Neutral
+    )));
+    assert!(mage.matches(&object(
+        EnemyAlly::Enemy,
+        Class::Sorcerer,
+        Alignment::Neutral
+    )));
+    assert!(!mage.matches(&object(EnemyAlly::Enemy, Class::Cleric, Alignment::Neutral)));
+    assert!(spec("[0.0.0.MAGE]").matches(&object(EnemyAlly::Pc, Class::Mage, Alignment::Neutral)));
+}
+
+#[test]
+fn matches_allegiance_cutoffs() {
+    let ea = |spec_ea: &str, ea| {
+        spec(&format!("[{spec_ea}]")).matches(&object(ea, Class::Fighter, Alignment::Neutral))
+    };
+
+    assert!(ea("GOODCUTOFF", EnemyAlly::Familiar));
+    assert!(!ea("GOODCUTOFF", EnemyAlly::Neutral));
+    assert!(ea("NOTGOOD", EnemyAlly::Neutral));
+    assert!(ea("EVILCUTOFF", EnemyAlly::CharmedPc));
+    assert!(!ea("ENEMY", EnemyAlly::CharmedPc));
+    assert!(ea("NOTEVIL", EnemyAlly::AreaObject));
+    assert!(!ea("NOTNEUTRAL", EnemyAlly::Neutral));
+    assert!(ea("0", EnemyAlly::Neutral));
+}
+
+#[test]
+fn matches_alignment_masks() {
+    let alignment = |spec_alignment: &str, alignment| {
+        spec(&format!("[0.0.0.0.0.0.{spec_alignment}]")).matches(&object(
+            EnemyAlly::Enemy,
+            Class::Fighter,
+            alignment,
+        ))
+    };
+
+    assert!(alignment("MASK_EVIL", Alignment::ChaoticEvil));
+    assert!(!alignment("MASK_EVIL", Alignment::LawfulGood));
+    assert!(alignment("MASK_LAWFUL", Alignment::LawfulNeutral));
+    assert!(alignment("CHAOTIC_EVIL", Alignment::ChaoticEvil));
+    assert!(!alignment("CHAOTIC_EVIL", Alignment::NeutralEvil));
+}
+
+#[test]
+fn matches_names() {
+    let xzar = object(EnemyAlly::Pc, Class::Mage, Alignment::ChaoticEvil);
+
+    assert!(spec("\"XZAR\"").matches(&xzar));
+    assert!(!spec("\"Montaron\"").matches(&xzar));
+}
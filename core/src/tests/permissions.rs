@@ -0,0 +1,53 @@
+use std::num::NonZero;
+
+use crate::permissions::{PermissionDiagnostics, parse_effective_capabilities, parse_real_uid};
+
+const STATUS: &str = "Name:\tBaldursGate
+Umask:\t0022
+State:\tS (sleeping)
+Uid:\t1000\t1000\t1000\t1000
+Gid:\t1000\t1000\t1000\t1000
+CapInh:\t0000000000000000
+CapPrm:\t0000000000000000
+CapEff:\t0000000000080000
+CapBnd:\t000001ffffffffff
+";
+
+fn diagnostics(ptrace_scope: Option<u32>, target_uid: Option<u32>) -> PermissionDiagnostics {
+    PermissionDiagnostics {
+        pid: NonZero::new(1234).unwrap(),
+        errors: vec![(
+            "process_vm_readv",
+            std::io::Error::from_raw_os_error(libc::EPERM),
+        )],
+        ptrace_scope,
+        has_cap_sys_ptrace: false,
+        own_uid: 1000,
+        target_uid,
+    }
+}
+
+#[test]
+fn parses_status() {
+    assert_eq!(parse_real_uid(STATUS), Some(1000));
+    assert_eq!(parse_effective_capabilities(STATUS), Some(1 << 19));
+    assert_eq!(parse_real_uid("Name:\tx\n"), None);
+}
+
+#[test]
+fn explains_ptrace_scope() {
+    let message = diagnostics(Some(1), Some(1000)).to_string();
+
+    assert!(message.contains("process 1234"), "{message}");
+    assert!(message.contains("ptrace_scope is 1"), "{message}");
+    assert!(message.contains("kernel.yama.ptrace_scope=0"), "{message}");
+    assert!(!message.contains("uid"), "{message}");
+}
+
+#[test]
+fn explains_other_user() {
+    let message = diagnostics(Some(0), Some(0)).to_string();
+
+    assert!(message.contains("runs as uid 0"), "{message}");
+    assert!(!message.contains("ptrace_scope is"), "{message}");
+}
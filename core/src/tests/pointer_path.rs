@@ -0,0 +1,124 @@
+use super::own_process;
+use crate::{
+    error::{Context, Error},
+    pointer_path::{MAX_READ_SIZE, PathRead, PathStart, PathStep, PathValue, PointerPath},
+};
+
+#[test]
+fn parses_paths() {
+    let path: PointerPath = "base+0xCBF780 -> [8] -> [0x3910] -> cstr(64)"
+        .parse()
+        .unwrap();
+
+    assert_eq!(
+        path,
+        PointerPath {
+            start: PathStart::Base,
+            steps: vec![
+                PathStep::Offset(0xCBF780),
+                PathStep::Deref(8),
+                PathStep::Deref(0x3910),
+            ],
+            read: Some(PathRead::CStr(64)),
+        }
+    );
+    assert_eq!(path.to_string().parse::<PointerPath>().unwrap(), path);
+
+    let path: PointerPath = "0x1000 [] [-0x10]-4 i16".parse().unwrap();
+    assert_eq!(path.start, PathStart::Address(0x1000));
+    assert_eq!(
+        path.steps,
+        [
+            PathStep::Deref(0),
+            PathStep::Deref(-0x10),
+            PathStep::Offset(-4)
+        ]
+    );
+    assert_eq!(path.read, Some(PathRead::I16));
+}
+
+#[test]
+fn rejects_invalid_paths() {
+    for s in [
+        "",
+        "basement",
+        "base + zz",
+        "base [8",
+        "base -> cstr",
+        "base -> u32 +4",
+        "base -> u33",
+    ] {
+        assert!(
+            matches!(
+                s.parse::<PointerPath>(),
+                Err(Error::InvalidPointerPath { .. })
+            ),
+            "{s:?} parsed"
+        );
+    }
+}
+
+#[test]
+fn limits_read_size() {
+    let path: PointerPath = format!("base -> bytes({MAX_READ_SIZE})").parse().unwrap();
+    assert_eq!(path.read, Some(PathRead::Bytes(MAX_READ_SIZE)));
+
+    for read in ["bytes", "cstr"] {
+        assert!(matches!(
+            format!("base -> {read}({})", MAX_READ_SIZE + 1).parse::<PointerPath>(),
+            Err(Error::InvalidPointerPath { .. })
+        ));
+    }
+}
+
+#[test]
+fn follows_pointers() {
+    #[repr(C)]
+    struct Inner {
+        padding: u32,
+        hp: i16,
+        name: [u8; 8],
+    }
+    #[repr(C)]
+    struct Outer {
+        padding: u64,
+        inner: *const Inner,
+    }
+
+    let process = own_process();
+    let inner = Inner {
+        padding: 0,
+        hp: -3,
+        name: *b"PLAYER1\0",
+    };
+    let outer = Outer {
+        padding: 0,
+        inner: &inner,
+    };
+    let base = &outer as *const Outer as usize;
+
+    let eval = |path: &str| path.parse::<PointerPath>().unwrap().eval(&process, base);
+
+    assert_eq!(
+        eval("base -> [8] +4 -> i16").unwrap(),
+        PathValue::Signed(-3)
+    );
+    assert_eq!(
+        eval("base -> [8] +6 -> cstr(8)").unwrap(),
+        PathValue::Text(Some("PLAYER1".into()))
+    );
+    assert_eq!(
+        eval("base -> [8]").unwrap(),
+        PathValue::Address(&inner as *const Inner as usize)
+    );
+
+    // `padding` is a null pointer
+    let error = eval("base -> [0] -> [0] -> u8").unwrap_err();
+    assert_eq!(
+        error.contexts().next(),
+        Some(&Context::PathStep {
+            index: 1,
+            step: "[0x0]".into(),
+        })
+    );
+}
@@ -0,0 +1,102 @@
+use std::fs::File;
+
+use super::own_process;
+use crate::{
+    error::Error,
+    process::{MemoryBackend, ProcessMemory, ReadRequest},
+};
+
+#[test]
+fn batch_reads_own_memory() {
+    let process = own_process();
+
+    // More requests than fit in a single process_vm_readv
+    let source: Vec<u64> = (0..3000).collect();
+    let mut destination = vec![0u64; source.len()];
+
+    let mut requests = source
+        .iter()
+        .zip(destination.iter_mut())
+        .map(|(src, dst)| ReadRequest {
+            address: src as *const u64 as usize,
+            buffer: as_bytes_mut(dst),
+        })
+        .collect::<Vec<_>>();
+
+    (&process).read_batch(&mut requests).unwrap();
+    drop(requests);
+
+    assert_eq!(source, destination);
+}
+
+#[test]
+fn batch_reports_failed_address() {
+    let process = own_process();
+
+    let value = 5u32;
+    let mut a = [0u8; 4];
+    let mut b = [0u8; 4];
+
+    let mut requests = [
+        ReadRequest {
+            address: &value as *const u32 as usize,
+            buffer: &mut a,
+        },
+        ReadRequest {
+            address: 0x10,
+            buffer: &mut b,
+        },
+    ];
+
+    match (&process).read_batch(&mut requests) {
+        Err(Error::Io(e)) => assert!(e.to_string().contains("0x10"), "{e}"),
+        x => panic!("Expected partial read, got {x:?}"),
+    }
+}
+
+fn as_bytes_mut(x: &mut u64) -> &mut [u8] {
+    unsafe { std::slice::from_raw_parts_mut((x as *mut u64).cast(), size_of::<u64>()) }
+}
+
+#[test]
+fn selects_process_vm_readv() {
+    let process = own_process();
+    let value = 5u32;
+
+    let backend =
+        MemoryBackend::select(&process.path, process.pid, &value as *const u32 as usize).unwrap();
+
+    assert!(matches!(backend, MemoryBackend::ProcessVmReadv));
+}
+
+#[test]
+fn reads_through_proc_mem() {
+    let mut process = own_process();
+    process.backend = MemoryBackend::ProcMem(File::open("/proc/self/mem").unwrap());
+
+    let value = 0x1234_5678u32;
+    let address = &value as *const u32 as usize;
+    assert_eq!(
+        (&process).read_mem(address, 4).unwrap(),
+        value.to_ne_bytes()
+    );
+
+    let mut a = [0u8; 4];
+    let mut b = [0u8; 4];
+    let mut requests = [
+        ReadRequest {
+            address,
+            buffer: &mut a,
+        },
+        ReadRequest {
+            address: 0x10,
+            buffer: &mut b,
+        },
+    ];
+
+    match (&process).read_batch(&mut requests) {
+        Err(Error::Io(e)) => assert!(e.to_string().contains("0x10"), "{e}"),
+        x => panic!("Expected failed read, got {x:?}"),
+    }
+    assert_eq!(a, value.to_ne_bytes());
+}
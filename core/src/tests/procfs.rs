@@ -0,0 +1,407 @@
+use std::{
+    fs::{create_dir_all, remove_dir_all, write},
+    os::unix::fs::symlink,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    error::Error,
+    procfs::{GameSelector, Procfs, is_alive, parse_boot_time, parse_start_ticks},
+    title::GameTitle,
+};
+
+const BASE_ADDRESS: usize = 0x5555_5555_4000;
+const BOOT_TIME: u64 = 1_700_000_000;
+
+/// A fake procfs tree in the temp directory, removed on drop
+pub(super) struct Fixture {
+    pub(super) root: PathBuf,
+}
+impl Fixture {
+    pub(super) fn new(name: &str) -> Self {
+        let root =
+            std::env::temp_dir().join(format!("bg-radar-procfs-{}-{name}", std::process::id()));
+        let _ = remove_dir_all(&root);
+        create_dir_all(root.join("bin")).unwrap();
+
+        write(
+            root.join("stat"),
+            format!("cpu  1 2 3 4\nbtime {BOOT_TIME}\n"),
+        )
+        .unwrap();
+
+        Self { root }
+    }
+
+    pub(super) fn procfs(&self) -> Procfs {
+        Procfs::new(&self.root)
+    }
+
+    /// Adds a running process with its executable mapped at [`BASE_ADDRESS`]
+    fn add(&self, pid: u32, name: &str) -> PathBuf {
+        let exe = self.executable(&format!("{name}-{pid}"));
+        self.add_mapping(pid, name, &exe, &exe, "")
+    }
+
+    fn executable(&self, name: &str) -> PathBuf {
+        let exe = self.root.join("bin").join(name);
+        write(&exe, "").unwrap();
+        exe
+    }
+
+    /// Adds a running process of `exe` with `mapped` at [`BASE_ADDRESS`]
+    fn add_mapping(
+        &self,
+        pid: u32,
+        comm: &str,
+        exe: &Path,
+        mapped: &Path,
+        status: &str,
+    ) -> PathBuf {
+        self.add_mapping_at(pid, comm, exe, mapped, status, BASE_ADDRESS)
+    }
+
+    /// Adds a running game with its executable mapped at `base_address`
+    pub(super) fn add_at(&self, pid: u32, name: &str, base_address: usize) -> PathBuf {
+        let exe = self.executable(&format!("{name}-{pid}"));
+        self.add_mapping_at(pid, name, &exe, &exe, "", base_address)
+    }
+
+    fn add_mapping_at(
+        &self,
+        pid: u32,
+        comm: &str,
+        exe: &Path,
+        mapped: &Path,
+        status: &str,
+        base_address: usize,
+    ) -> PathBuf {
+        let maps = format!(
+            "{:x}-{:x} r--p 00000000 00:00 1 {path}\n\
+             {:x}-{:x} r-xp 00001000 00:00 1 {path}\n\
+             7fff0000-7fff1000 rw-p 00000000 00:00 0 [stack]\n",
+            base_address,
+            base_address + 0x1000,
+            base_address + 0x1000,
+            base_address + 0x2000,
+            path = mapped.display(),
+        );
+
+        let path = self.add_with(pid, comm, &format!("S (sleeping)\n{status}"), &maps);
+        symlink(exe, path.join("exe")).unwrap();
+
+        path
+    }
+
+    pub(super) fn add_with(&self, pid: u32, name: &str, state: &str, maps: &str) -> PathBuf {
+        let path = self.root.join(pid.to_string());
+        create_dir_all(&path).unwrap();
+
+        write(path.join("comm"), format!("{name}\n")).unwrap();
+        write(
+            path.join("status"),
+            format!("Name:\t{name}\nState:\t{state}\n"),
+        )
+        .unwrap();
+        write(path.join("maps"), maps).unwrap();
+
+        path
+    }
+
+    /// Sets the start time of the process at `path` to `ticks` clock ticks after boot
+    pub(super) fn started(&self, path: &Path, ticks: u64) {
+        let fields = std::iter::repeat_n("0", 18).collect::<Vec<_>>().join(" ");
+        write(
+            path.join("stat"),
+            format!("1 (Baldurs Gate) S {fields} {ticks} 0\n"),
+        )
+        .unwrap();
+    }
+}
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let _ = remove_dir_all(&self.root);
+    }
+}
+
+fn find_games(procfs: &Procfs) -> Result<Vec<(u32, String)>, Error> {
+    let mut games = procfs
+        .find_games()?
+        .map(|x| x.map(|info| (info.pid.get(), info.name)))
+        .collect::<Result<Vec<_>, _>>()?;
+    games.sort();
+
+    Ok(games)
+}
+
+#[test]
+fn detects_each_title() {
+    let fixture = Fixture::new("titles");
+    fixture.add(100, "BaldursGate");
+    fixture.add(200, "BaldursGateII");
+    fixture.add(300, "bash");
+    create_dir_all(fixture.root.join("self")).unwrap();
+
+    assert_eq!(
+        find_games(&fixture.procfs()).unwrap(),
+        [
+            (100, "BaldursGate".to_string()),
+            (200, "BaldursGateII".to_string())
+        ]
+    );
+
+    let info = fixture
+        .procfs()
+        .find_games()
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap();
+    assert_eq!(info.base_address.get(), BASE_ADDRESS);
+    assert_eq!(info.path, fixture.root.join(info.pid.to_string()));
+}
+
+#[test]
+fn finds_multiple_instances() {
+    let fixture = Fixture::new("instances");
+    fixture.add(100, "BaldursGate");
+    fixture.add(101, "BaldursGate");
+
+    assert_eq!(
+        find_games(&fixture.procfs()).unwrap(),
+        [
+            (100, "BaldursGate".to_string()),
+            (101, "BaldursGate".to_string())
+        ]
+    );
+}
+
+#[test]
+fn skips_exited_processes() {
+    let fixture = Fixture::new("exited");
+    let running = fixture.add(100, "BaldursGate");
+    let zombie = fixture.add_with(101, "BaldursGate", "Z (zombie)", "");
+    // Exited between listing /proc and reading its entries
+    let vanished = fixture.root.join("102");
+    create_dir_all(&vanished).unwrap();
+
+    assert_eq!(
+        find_games(&fixture.procfs()).unwrap(),
+        [(100, "BaldursGate".to_string())]
+    );
+
+    assert!(is_alive(&running));
+    assert!(!is_alive(&zombie));
+    assert!(!is_alive(&vanished));
+}
+
+#[test]
+fn reports_malformed_maps() {
+    let fixture = Fixture::new("malformed");
+    let path = fixture.add(100, "BaldursGate");
+    write(path.join("maps"), "not a mapping\n").unwrap();
+
+    assert!(matches!(
+        find_games(&fixture.procfs()),
+        Err(Error::InvalidMemoryMap { line: 1, .. })
+    ));
+
+    // Rather than claiming no game is running
+    assert!(matches!(
+        fixture.procfs().find_game_process(true),
+        Err(Error::InvalidMemoryMap { .. })
+    ));
+}
+
+#[test]
+fn reports_missing_game() {
+    let fixture = Fixture::new("missing");
+    fixture.add(100, "bash");
+
+    assert!(matches!(
+        fixture.procfs().find_game_process(true),
+        Err(Error::MissingGameProcess)
+    ));
+    assert!(matches!(
+        fixture.procfs().find_game_process(false),
+        Err(Error::GameProcessClosed)
+    ));
+}
+
+#[test]
+fn matches_by_executable_name() {
+    let fixture = Fixture::new("exe-name");
+    let exe = fixture.executable("BaldursGate");
+    fixture.add_mapping(100, "GameThread", &exe, &exe, "");
+
+    let info = fixture
+        .procfs()
+        .find_games()
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(info.name, "BaldursGate");
+    assert_eq!(info.exe, exe);
+    assert_eq!(info.exe_file, fixture.root.join("100").join("exe"));
+}
+
+#[test]
+fn finds_game_started_by_loader() {
+    let fixture = Fixture::new("loader");
+    let loader = fixture.executable("ld-linux-x86-64.so.2");
+    let exe = fixture.executable("BaldursGateII");
+    fixture.add_mapping(100, "ld-linux-x86-64", &loader, &exe, "");
+
+    let loader_only = fixture.executable("other");
+    fixture.add_mapping(101, "ld-linux-x86-64", &loader, &loader_only, "");
+
+    let games = fixture
+        .procfs()
+        .find_games()
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(games.len(), 1);
+
+    let info = &games[0];
+    assert_eq!(info.name, "BaldursGateII");
+    assert_eq!(info.exe, exe);
+    assert_eq!(info.base_address.get(), BASE_ADDRESS);
+    assert_eq!(
+        info.exe_file,
+        fixture
+            .root
+            .join("100")
+            .join("root")
+            .join(exe.strip_prefix("/").unwrap())
+    );
+}
+
+#[test]
+fn reports_nested_namespace() {
+    let fixture = Fixture::new("namespace");
+    let exe = fixture.executable("BaldursGate");
+    let path = fixture.add_mapping(100, "BaldursGate", &exe, &exe, "NSpid:\t100\t7\n");
+    create_dir_all(path.join("ns")).unwrap();
+    symlink("pid:[4026532000]", path.join("ns").join("pid")).unwrap();
+
+    let info = fixture
+        .procfs()
+        .find_games()
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(info.pid.get(), 100);
+    assert_eq!(info.namespace.inode, Some(4026532000));
+    assert!(info.namespace.is_nested());
+    assert_eq!(info.namespace.local_pid().map(|x| x.get()), Some(7));
+    assert_eq!(
+        info.namespace.to_string(),
+        "nested pid namespace 4026532000 as pid 7"
+    );
+}
+
+#[test]
+fn detects_title_through_process_root() {
+    let fixture = Fixture::new("title");
+    let exe = fixture.executable("BaldursGate");
+    let path = fixture.add_mapping(100, "BaldursGate", &exe, &exe, "");
+
+    let game_dir = path
+        .join("root")
+        .join(exe.parent().unwrap().strip_prefix("/").unwrap());
+    create_dir_all(game_dir.join("dlc")).unwrap();
+    write(game_dir.join("dlc").join("sod-dlc.zip"), "").unwrap();
+
+    let info = fixture
+        .procfs()
+        .find_games()
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap();
+    assert_eq!(info.title, GameTitle::SoD);
+}
+
+#[test]
+fn parses_stat() {
+    let stat = "4242 (a) b) (c) S 1 4242 4242 0 -1 4194560 100 0 0 0 5 3 0 0 20 0 12 0 \
+                98765 1000 200 18446744073709551615\n";
+
+    assert_eq!(parse_start_ticks(stat), Some(98765));
+    assert_eq!(parse_start_ticks("4242 (truncated"), None);
+    assert_eq!(parse_start_ticks("4242 (short) S 1 2 3"), None);
+
+    assert_eq!(
+        parse_boot_time("cpu  1 2 3\nintr 5\nbtime 1700000000\nprocesses 10\n"),
+        Some(1_700_000_000)
+    );
+    assert_eq!(parse_boot_time("cpu  1 2 3\n"), None);
+}
+
+#[test]
+fn lists_games_oldest_first() {
+    let fixture = Fixture::new("list");
+    let bgee = fixture.add(100, "BaldursGate");
+    let bg2ee = fixture.add(200, "BaldursGateII");
+    let unknown = fixture.add(300, "BaldursGate");
+    let also_unknown = fixture.add(50, "BaldursGateII");
+    fixture.add(400, "bash");
+
+    let ticks_per_second = unsafe { libc::sysconf(libc::_SC_CLK_TCK) } as u64;
+    fixture.started(&bgee, 20 * ticks_per_second);
+    fixture.started(&bg2ee, 10 * ticks_per_second);
+    write(unknown.join("stat"), "300 (BaldursGate) S 1\n").unwrap();
+    write(also_unknown.join("stat"), "50 (BaldursGateII) S 1\n").unwrap();
+
+    let games = fixture.procfs().list_games().unwrap();
+    let games = games
+        .iter()
+        .map(|x| (x.pid.get(), x.title, x.start_time))
+        .collect::<Vec<_>>();
+
+    let boot = SystemTime::UNIX_EPOCH + Duration::from_secs(BOOT_TIME);
+    // Unknown start times last, by pid, rather than as the oldest
+    assert_eq!(
+        games,
+        [
+            (200, GameTitle::Bg2ee, Some(boot + Duration::from_secs(10))),
+            (100, GameTitle::Bgee, Some(boot + Duration::from_secs(20))),
+            (50, GameTitle::Bg2ee, None),
+            (300, GameTitle::Bgee, None),
+        ]
+    );
+}
+
+#[test]
+fn selects_game_by_pid_and_title() {
+    let fixture = Fixture::new("select");
+    fixture.add(100, "BaldursGate");
+    fixture.add(200, "BaldursGateII");
+
+    let games = fixture.procfs().list_games().unwrap();
+    let selected = |selector: GameSelector| {
+        games
+            .iter()
+            .filter(|x| selector.matches(x))
+            .map(|x| x.pid.get())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(selected(GameSelector::Any), [100, 200]);
+    assert_eq!(selected(GameSelector::Pid(200.try_into().unwrap())), [200]);
+    assert_eq!(selected(GameSelector::Title(GameTitle::Bgee)), [100]);
+    assert!(selected(GameSelector::Title(GameTitle::Iwdee)).is_empty());
+
+    assert!(matches!(
+        fixture
+            .procfs()
+            .find_game_process_by(&GameSelector::Title(GameTitle::Iwdee), true),
+        Err(Error::MissingGameProcess)
+    ));
+}
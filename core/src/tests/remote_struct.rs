@@ -0,0 +1,128 @@
+use std::{ffi::c_void, ptr::null};
+
+use super::own_process;
+use crate::{
+    ids::enemy_ally::EnemyAlly,
+    remote_ptr::RemotePtr,
+    remote_struct::{FieldKind, ReadContext, RemoteStruct},
+    types::{CDerivedStats, CDerivedStatsLayout, CGameEffect, CGameObject, CGameSprite, Lookup},
+};
+
+#[test]
+fn describes_fields() {
+    let effect_id = CGameEffect::FIELDS
+        .iter()
+        .find(|x| x.name == "effect_id")
+        .unwrap();
+    // Includes the base class
+    assert_eq!(effect_id.offset, 0x10);
+    assert_eq!(effect_id.size, 4);
+    assert_eq!(effect_id.kind, FieldKind::Lookup);
+
+    let type_ai = CGameObject::FIELDS
+        .iter()
+        .find(|x| x.name == "type_ai")
+        .unwrap();
+    assert_eq!(type_ai.kind, FieldKind::Nested("CAIObjectType"));
+    assert_eq!(type_ai.size, 0x18);
+
+    assert_eq!(CGameObject::NAME, "CGameAIBase");
+    assert_eq!(CDerivedStats::SIZE, 0x2F4);
+}
+
+#[test]
+fn finds_offsets_in_layout() {
+    let process = own_process();
+    let layout = CGameEffect::layout(&process.layout);
+
+    assert_eq!(CGameEffect::offset_of(layout, "script_name"), Some(0xA8));
+    assert_eq!(CGameEffect::offset_of(layout, "missing"), None);
+}
+
+#[test]
+fn defaults_missing_offsets() {
+    let layout: CDerivedStatsLayout = toml::from_str("max_hp = 0x40").unwrap();
+
+    assert_eq!(layout.max_hp, 0x40);
+    assert_eq!(layout.ac, 0x6);
+    assert_eq!(layout.size, CDerivedStats::SIZE);
+
+    assert!(toml::from_str::<CDerivedStatsLayout>("hit_points = 0x4").is_err());
+}
+
+#[test]
+fn reads_nested_structs() {
+    let process = own_process();
+    let ctx = ReadContext::new(&process);
+
+    let name = b"PLAYER1\0";
+    let mut bytes = [0u8; CGameObject::SIZE];
+    bytes[0x8] = 0x31;
+    bytes[0x30..0x38].copy_from_slice(&(name.as_ptr() as usize).to_ne_bytes());
+    bytes[0x38] = 2;
+
+    let object = CGameObject::read(&ctx, RemotePtr::<c_void>::new(bytes.as_ptr().cast())).unwrap();
+
+    assert!(object.is_sprite());
+    assert_eq!(object.type_ai.name.as_deref(), Some("PLAYER1"));
+    assert_eq!(object.type_ai.enemy_ally, Lookup::Found(EnemyAlly::Pc));
+}
+
+/// `CPtrListNode` as laid out in the built in layout
+#[repr(C)]
+struct Node {
+    next: *const Node,
+    prev: *const Node,
+    data: *const c_void,
+}
+
+#[test]
+fn reads_lists_and_collects_their_errors() {
+    let process = own_process();
+    let ctx = ReadContext::new(&process);
+    let layout = CGameSprite::layout(&process.layout);
+
+    let mut effect = [0u8; CGameEffect::SIZE];
+    effect[0x10..0x14].copy_from_slice(&12u32.to_ne_bytes());
+
+    // The second effect is unmapped, as if freed while being read
+    let mut nodes = [
+        Node {
+            next: null(),
+            prev: null(),
+            data: effect.as_ptr().cast(),
+        },
+        Node {
+            next: null(),
+            prev: null(),
+            data: 0x10 as *const c_void,
+        },
+    ];
+    nodes[0].next = &nodes[1];
+
+    let name = [0u8; 64];
+    let mut bytes = vec![0u8; CGameSprite::SIZE];
+    let mut write = |offset: isize, value: &[u8]| {
+        let offset = offset as usize;
+        bytes[offset..offset + value.len()].copy_from_slice(value);
+    };
+    write(layout.name, &(name.as_ptr() as usize).to_ne_bytes());
+    let list = layout.timed_effects;
+    write(
+        list + process.layout.ptr_list.head,
+        &(nodes.as_ptr() as usize).to_ne_bytes(),
+    );
+    write(list + process.layout.ptr_list.count, &2u32.to_ne_bytes());
+
+    let sprite = CGameSprite::read(&ctx, RemotePtr::new(bytes.as_ptr().cast())).unwrap();
+
+    assert!(sprite.equipped_effects.is_empty());
+    assert_eq!(sprite.timed_effects.len(), 1);
+    assert_eq!(sprite.timed_effects[0].effect_id.raw(), 12);
+    assert_eq!(sprite.effect_errors.len(), 1);
+    assert!(
+        sprite.effect_errors[0]
+            .to_string()
+            .starts_with("CGameSprite.timed_effects at ")
+    );
+}
@@ -0,0 +1,149 @@
+use crate::{
+    build_id::BuildId,
+    error::Error,
+    layout::GlobalsLayout,
+    signature::{ENTITY_LIST, GlobalSignature, Globals, Scanner, Signature, resolve_rip_relative},
+};
+
+#[test]
+fn parses_wildcards() {
+    let signature: Signature = "48 8D ?? ? 05".parse().unwrap();
+
+    assert_eq!(signature.len(), 5);
+    assert!(signature.matches(&[0x48, 0x8D, 0x00, 0xFF, 0x05]));
+    assert!(!signature.matches(&[0x48, 0x8D, 0x00, 0xFF, 0x06]));
+    assert!(!signature.matches(&[0x48, 0x8D, 0x00, 0xFF]));
+}
+
+#[test]
+fn rejects_invalid_signatures() {
+    for s in ["", "48 8D 0", "48 GG", "488D"] {
+        assert!(
+            matches!(s.parse::<Signature>(), Err(Error::InvalidSignature { .. })),
+            "{s:?} parsed"
+        );
+    }
+}
+
+#[test]
+fn finds_all_matches() {
+    let signature: Signature = "AA ?? CC".parse().unwrap();
+    let haystack = [0xAA, 0x00, 0xCC, 0xAA, 0xBB, 0xCC, 0xAA, 0xCC];
+
+    assert_eq!(signature.find(&haystack), Some(0));
+    assert_eq!(signature.find_all(&haystack).collect::<Vec<_>>(), [0, 3]);
+    assert_eq!(signature.find(&haystack[..2]), None);
+}
+
+#[test]
+fn resolves_rip_relative() {
+    assert_eq!(resolve_rip_relative(0x1000, 0x20), 0x1020);
+    assert_eq!(resolve_rip_relative(0x1000, -0x20), 0xFE0);
+}
+
+#[test]
+fn locates_global() {
+    const LEA: GlobalSignature = GlobalSignature {
+        name: "test",
+        pattern: "90 48 8D 05 ?? ?? ?? ?? C3",
+        displacement_offset: 4,
+        instruction_end: 8,
+    };
+
+    let mut code = vec![0xCC; 0x10];
+    code.extend([0x90, 0x48, 0x8D, 0x05]);
+    code.extend(0x100i32.to_le_bytes());
+    code.push(0xC3);
+
+    let scanner = Scanner::from_regions(vec![(0x4000, code)]);
+
+    // lea ends at 0x4000 + 0x10 + 8
+    assert_eq!(scanner.locate(&LEA).unwrap(), 0x4018 + 0x100);
+}
+
+#[test]
+fn reports_missing_signature() {
+    let scanner = Scanner::from_regions(vec![(0x4000, vec![0x90; 0x100])]);
+
+    assert!(matches!(
+        Globals::scan(&scanner, 0x4000, &GlobalsLayout::default()),
+        Err(Error::SignatureNotFound {
+            name: "entity_list"
+        })
+    ));
+}
+
+#[test]
+fn falls_back_to_known_offset() {
+    let scanner = Scanner::from_regions(vec![(0x4000, vec![0x90; 0x100])]);
+    let known = GlobalsLayout {
+        entity_list: Some(0xCBF780),
+    };
+
+    assert_eq!(
+        Globals::scan(&scanner, 0x4000, &known).unwrap(),
+        Globals {
+            entity_list: 0xCBF780
+        }
+    );
+}
+
+#[test]
+fn rejects_global_below_base_address() {
+    // lea [rip - 0x100], which lands before the executable
+    let mut code = vec![0x0F, 0xB7, 0xC9, 0x48, 0xC1, 0xE1, 0x04, 0x48, 0x8D, 0x05];
+    code.extend((-0x100i32).to_le_bytes());
+    code.extend([0x48, 0x8B, 0x44, 0x01, 0x08]);
+
+    let scanner = Scanner::from_regions(vec![(0x4000, code)]);
+
+    assert_eq!(scanner.locate(&ENTITY_LIST).unwrap(), 0x4000 + 14 - 0x100);
+    assert!(matches!(
+        Globals::scan(&scanner, 0x4000, &GlobalsLayout::default()),
+        Err(Error::Memory(_))
+    ));
+}
+
+#[test]
+fn reads_gnu_build_id() {
+    let build_id = [0xDE, 0xAD, 0xBE, 0xEF, 0x01];
+
+    let note = {
+        let mut note = vec![];
+        note.extend(4u32.to_le_bytes());
+        note.extend((build_id.len() as u32).to_le_bytes());
+        note.extend(3u32.to_le_bytes());
+        note.extend(b"GNU\0");
+        note.extend(build_id);
+        note.resize(note.len().div_ceil(4) * 4, 0);
+        note
+    };
+
+    let mut elf = vec![0; 0x40 + 0x38];
+    elf[..6].copy_from_slice(b"\x7fELF\x02\x01");
+    elf[0x20..0x28].copy_from_slice(&0x40u64.to_le_bytes());
+    elf[0x36..0x38].copy_from_slice(&0x38u16.to_le_bytes());
+    elf[0x38..0x3A].copy_from_slice(&1u16.to_le_bytes());
+
+    // PT_NOTE program header
+    elf[0x40..0x44].copy_from_slice(&4u32.to_le_bytes());
+    elf[0x48..0x50].copy_from_slice(&(0x78u64).to_le_bytes());
+    elf[0x60..0x68].copy_from_slice(&(note.len() as u64).to_le_bytes());
+    elf.extend(note);
+
+    assert_eq!(BuildId::from_elf(&elf), Some(BuildId(build_id.to_vec())));
+    assert_eq!(BuildId(build_id.to_vec()).to_string(), "deadbeef01");
+}
+
+#[test]
+fn hashes_non_elf_files() {
+    assert_eq!(BuildId::from_elf(b"#!/bin/sh"), None);
+    assert_eq!(
+        BuildId::from_contents(b"abc"),
+        BuildId::from_contents(b"abc")
+    );
+    assert_ne!(
+        BuildId::from_contents(b"abc"),
+        BuildId::from_contents(b"abd")
+    );
+}
@@ -0,0 +1,104 @@
+use std::ffi::c_void;
+
+use super::own_process;
+use crate::{
+    error::{Context, Error},
+    remote_ptr::RemotePtr,
+    snapshot::Snapshot,
+};
+
+#[test]
+fn reads_fields_from_snapshot() {
+    let bytes = (0u8..16).collect::<Vec<_>>();
+    let snapshot = Snapshot::from_bytes("Test", 0x1000, bytes);
+    let view = snapshot.view();
+
+    assert_eq!(view.get::<u16>(2).unwrap(), 0x0302);
+    assert_eq!(view.bytes(4, 3).unwrap(), &[4, 5, 6]);
+
+    let nested = view.at(8).unwrap();
+    assert_eq!(nested.address(), 0x1008);
+    assert_eq!(nested.len(), 8);
+    assert_eq!(nested.get::<u8>(1).unwrap(), 9);
+}
+
+#[test]
+fn rejects_out_of_bounds_fields() {
+    let snapshot = Snapshot::from_bytes("Test", 0x1000, vec![0; 8]);
+    let view = snapshot.view();
+
+    assert!(matches!(
+        view.get::<u32>(6),
+        Err(Error::SnapshotOutOfBounds {
+            offset: 6,
+            length: 4,
+            size: 8
+        })
+    ));
+    assert!(view.get::<u8>(-1).is_err());
+    assert!(view.at(9).is_err());
+    assert!(view.at(8).unwrap().is_empty());
+}
+
+#[test]
+fn names_fields_in_errors() {
+    let snapshot = Snapshot::from_bytes("Outer", 0x1000, vec![0; 8]);
+    let inner = snapshot.view().at(4).unwrap().named("Inner");
+
+    let error = inner.field::<u32>("value", 2).unwrap_err();
+
+    assert_eq!(
+        error.contexts().collect::<Vec<_>>(),
+        [&Context::Field {
+            structure: "Inner",
+            field: "value",
+            address: 0x1006
+        }]
+    );
+    assert!(matches!(error.root(), Error::SnapshotOutOfBounds { .. }));
+    assert_eq!(error.to_string(), "Inner.value at 0x1006");
+    assert_eq!(
+        error.report(),
+        "Inner.value at 0x1006: 4 bytes at offset 0x2 are outside of the 0x4 byte snapshot"
+    );
+}
+
+#[test]
+fn reads_many_snapshots() {
+    let process = own_process();
+
+    let values: [[u32; 2]; 3] = [[1, 2], [3, 4], [5, 6]];
+    let ptrs = values
+        .iter()
+        .map(|x| RemotePtr::new(x.as_ptr() as *const c_void))
+        .collect::<Vec<_>>();
+
+    let snapshots = Snapshot::read_many(&process, "Test", &ptrs, size_of::<[u32; 2]>()).unwrap();
+
+    let read = snapshots
+        .iter()
+        .map(|x| [x.view().get(0).unwrap(), x.view().get(4).unwrap()])
+        .collect::<Vec<[u32; 2]>>();
+    assert_eq!(read, values);
+}
+
+#[test]
+fn reports_failed_reads() {
+    let process = own_process();
+
+    let error =
+        Snapshot::read(&process, "Test", RemotePtr::new(0x10 as *const c_void), 4).unwrap_err();
+
+    assert_eq!(
+        error.contexts().collect::<Vec<_>>(),
+        [&Context::Read {
+            structure: "Test",
+            address: 0x10,
+            size: 4
+        }]
+    );
+    assert!(matches!(error.root(), Error::Io(_)));
+
+    let source = std::error::Error::source(&error).expect("Missing source");
+    assert_eq!(source.to_string(), error.root().to_string());
+}
@@ -0,0 +1,84 @@
+use std::fs::{create_dir_all, remove_dir_all, write};
+
+use crate::{
+    ids::{IdsFile, IdsTables, classes::Class, race::Race},
+    title::GameTitle,
+    types::Lookup,
+};
+
+#[test]
+fn detects_title_from_executable() {
+    assert_eq!(
+        GameTitle::from_executable_name("BaldursGate"),
+        Some(GameTitle::Bgee)
+    );
+    assert_eq!(
+        GameTitle::from_executable_name("BaldursGateII64"),
+        Some(GameTitle::Bg2ee)
+    );
+    assert_eq!(
+        GameTitle::from_executable_name("IcewindDale"),
+        Some(GameTitle::Iwdee)
+    );
+    assert_eq!(
+        GameTitle::from_executable_name("Torment64"),
+        Some(GameTitle::Pstee)
+    );
+    assert_eq!(GameTitle::from_executable_name("bash"), None);
+}
+
+#[test]
+fn detects_dlc_and_mods() {
+    let dir = std::env::temp_dir().join(format!("bg-radar-title-{}", std::process::id()));
+    let _ = remove_dir_all(&dir);
+    create_dir_all(dir.join("dlc")).unwrap();
+
+    assert_eq!(
+        GameTitle::detect("BaldursGate", &dir),
+        Some(GameTitle::Bgee)
+    );
+    assert_eq!(
+        GameTitle::detect("BaldursGateII", &dir),
+        Some(GameTitle::Bg2ee)
+    );
+
+    write(dir.join("dlc").join("sod-dlc.zip"), "").unwrap();
+    create_dir_all(dir.join("EET")).unwrap();
+
+    assert_eq!(GameTitle::detect("BaldursGate", &dir), Some(GameTitle::SoD));
+    assert_eq!(
+        GameTitle::detect("BaldursGateII", &dir),
+        Some(GameTitle::Eet)
+    );
+    assert_eq!(
+        GameTitle::detect("IcewindDale", &dir),
+        Some(GameTitle::Iwdee)
+    );
+
+    remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn parses_abbreviations() {
+    for title in GameTitle::ALL {
+        assert_eq!(title.to_string().parse::<GameTitle>().unwrap(), title);
+    }
+
+    assert_eq!("bg2ee".parse::<GameTitle>().unwrap(), GameTitle::Bg2ee);
+    assert!("BG3".parse::<GameTitle>().is_err());
+}
+
+#[test]
+fn selects_ids_tables() {
+    assert_eq!(GameTitle::Eet.ids(), IdsTables::BaldursGate);
+
+    let bg = IdsTables::BaldursGate;
+    let iwd = IdsTables::IcewindDale;
+    let pst = IdsTables::Torment;
+
+    assert_eq!(bg.lookup(IdsFile::Race, 101), Lookup::Found(Race::Ankheg));
+    assert_eq!(iwd.lookup(IdsFile::Race, 1), Lookup::Found(Race::Human));
+    assert_eq!(iwd.lookup::<Race>(IdsFile::Race, 101), Lookup::Unknown(101));
+    assert_eq!(pst.lookup::<Class>(IdsFile::Class, 1), Lookup::Unknown(1));
+    assert_eq!(bg.lookup::<Race>(IdsFile::Race, 0), Lookup::Unknown(0));
+}
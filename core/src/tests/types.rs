@@ -0,0 +1,129 @@
+use super::{decode_zeroed, own_process};
+use crate::{
+    ids::{proficiency::Proficiency, state::State},
+    language::Language,
+    remote_ptr::RemotePtr,
+    remote_struct::ReadContext,
+    types::{CDerivedStats, CGameEffect, Lookup, ObjectType, SpellStates},
+};
+
+fn effect_with_id(effect_id: u32) -> CGameEffect {
+    let process = own_process();
+    let ctx = ReadContext::new(&process);
+
+    decode_zeroed::<CGameEffect>(&ctx, |layout, bytes| {
+        let offset = (layout.base + layout.effect_id) as usize;
+        bytes[offset..offset + 4].copy_from_slice(&effect_id.to_ne_bytes());
+    })
+}
+
+#[test]
+fn decodes_known_values() {
+    assert_eq!(
+        Lookup::decode(0x31),
+        Lookup::<ObjectType, u8>::Found(ObjectType::Sprite)
+    );
+
+    let effect = format!("{:?}", effect_with_id(12));
+    assert!(effect.contains("Found(HPDamage)"), "{effect}");
+}
+
+#[test]
+fn keeps_unknown_values() {
+    assert_eq!(
+        Lookup::<ObjectType, u8>::decode(0xFE),
+        Lookup::Unknown(0xFE)
+    );
+
+    // e.g. an opcode added by a mod
+    let effect = format!("{:?}", effect_with_id(9999));
+    assert!(effect.contains("Unknown(9999)"), "{effect}");
+}
+
+#[test]
+fn reads_plain_data() {
+    let process = own_process();
+
+    let value: [u16; 3] = [1, 2, 3];
+    let ptr = RemotePtr::new(value.as_ptr());
+
+    assert_eq!(ptr.read(&process).unwrap(), 1);
+    assert_eq!(ptr.read_array(&process, 3).unwrap(), value);
+    assert_eq!(ptr.cast::<[u16; 3]>().read(&process).unwrap(), value);
+    assert!(
+        RemotePtr::<u32>::new(0x10 as *const u32)
+            .read(&process)
+            .is_err()
+    );
+}
+
+#[test]
+fn decodes_legacy_script_names() {
+    let process = own_process();
+    let ctx = ReadContext {
+        language: Language::Polish,
+        ..ReadContext::new(&process)
+    };
+
+    // "Łucznik" in Windows-1250, which used to be rejected as invalid UTF-8
+    let effect = decode_zeroed::<CGameEffect>(&ctx, |layout, bytes| {
+        let offset = (layout.base + layout.script_name) as usize;
+        bytes[offset..offset + 7].copy_from_slice(b"\xA3ucznik");
+    });
+
+    let effect = format!("{effect:?}");
+    assert!(effect.contains("script_name: \"Łucznik\""), "{effect}");
+}
+
+#[test]
+fn decodes_general_state() {
+    let process = own_process();
+    let ctx = ReadContext::new(&process);
+
+    let decode = |state: State| {
+        decode_zeroed::<CDerivedStats>(&ctx, |layout, bytes| {
+            let offset = layout.general_state as usize;
+            bytes[offset..offset + 4].copy_from_slice(&state.bits().to_ne_bytes());
+        })
+        .general_state
+    };
+
+    let stunned = decode(State::STUNNED | State::HASTED);
+    assert!(stunned.is_disabled());
+    assert!(!stunned.is_dead());
+
+    let petrified = decode(State::STONE_DEATH);
+    assert!(petrified.is_dead());
+    assert!(petrified.is_disabled());
+
+    // Charmed creatures act for someone else, silenced ones can still fight
+    assert!(decode(State::CHARMED).is_disabled());
+    assert!(!decode(State::SILENCED).is_disabled());
+    assert_eq!(State::DISABLED.bits(), 0x80102FEF);
+
+    let invisible = decode(State::IMPROVED_INVISIBILITY);
+    assert!(invisible.is_invisible());
+    assert!(!invisible.is_disabled());
+
+    // Bits STATE.IDS doesn't name are kept
+    assert_eq!(decode(State::from_bits_retain(u32::MAX)).bits(), u32::MAX);
+}
+
+#[test]
+fn reads_proficiencies_and_spell_states() {
+    let process = own_process();
+    let ctx = ReadContext::new(&process);
+
+    let stats = decode_zeroed::<CDerivedStats>(&ctx, |layout, bytes| {
+        let offset = layout.proficiencies as usize + 4 * 7;
+        bytes[offset..offset + 4].copy_from_slice(&3i32.to_ne_bytes());
+        let offset = layout.spell_states as usize + 4;
+        bytes[offset] = 0b10;
+    });
+
+    assert_eq!(stats.proficiency(Proficiency::Dagger), 3);
+    assert_eq!(stats.proficiency(Proficiency::BastardSword), 0);
+    assert!(stats.spell_states.contains(33));
+    assert_eq!(stats.spell_states.iter().collect::<Vec<_>>(), [33]);
+    assert!(!SpellStates::default().contains(u8::MAX));
+}
@@ -0,0 +1,163 @@
+use std::{
+    fs::{read_to_string, write},
+    num::{NonZeroU32, NonZeroUsize},
+    path::{Path, PathBuf},
+    process::{Child, Command},
+};
+
+use super::procfs::Fixture;
+use crate::{
+    error::Error,
+    watcher::{GameProcessWatcher, WatchEvent},
+};
+
+/// A real process for the watcher to attach to, as attaching reads the game's memory. The
+/// fixture stands in for its procfs entry, with the executable at its first real mapping
+struct Game {
+    child: Child,
+    pid: NonZeroU32,
+    base_address: NonZeroUsize,
+}
+impl Game {
+    fn spawn() -> Self {
+        let child = Command::new("sleep").arg("60").spawn().unwrap();
+        let pid = NonZeroU32::new(child.id()).unwrap();
+
+        let maps = read_to_string(format!("/proc/{pid}/maps")).unwrap();
+        let start = maps.split('-').next().unwrap();
+        let base_address = usize::from_str_radix(start, 16).unwrap();
+
+        Self {
+            child,
+            pid,
+            base_address: NonZeroUsize::new(base_address).unwrap(),
+        }
+    }
+
+    fn add_to(&self, fixture: &Fixture) -> PathBuf {
+        fixture.add_at(self.pid.get(), "BaldursGate", self.base_address.get())
+    }
+}
+impl Drop for Game {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn exit(path: &Path) {
+    write(
+        path.join("status"),
+        "Name:\tBaldursGate\nState:\tZ (zombie)\n",
+    )
+    .unwrap();
+}
+
+#[test]
+fn attaches_and_detaches() {
+    let fixture = Fixture::new("watch-detach");
+    let mut watcher = GameProcessWatcher::with_procfs(fixture.procfs());
+    assert_eq!(watcher.poll().unwrap(), None);
+
+    let game = Game::spawn();
+    let path = game.add_to(&fixture);
+
+    assert_eq!(
+        watcher.poll().unwrap(),
+        Some(WatchEvent::Attached {
+            pid: game.pid,
+            base_address: game.base_address
+        })
+    );
+    assert!(watcher.is_attached());
+    assert_eq!(watcher.poll().unwrap(), None);
+
+    exit(&path);
+    assert_eq!(
+        watcher.poll().unwrap(),
+        Some(WatchEvent::Detached { pid: game.pid })
+    );
+    assert!(!watcher.is_attached());
+    assert_eq!(watcher.poll().unwrap(), None);
+}
+
+#[test]
+fn reports_restarts() {
+    let fixture = Fixture::new("watch-restart");
+    let mut watcher = GameProcessWatcher::with_procfs(fixture.procfs());
+
+    let old = Game::spawn();
+    let old_path = old.add_to(&fixture);
+    assert!(matches!(
+        watcher.poll().unwrap(),
+        Some(WatchEvent::Attached { .. })
+    ));
+
+    // Relaunched between two polls
+    exit(&old_path);
+    let new = Game::spawn();
+    new.add_to(&fixture);
+
+    assert_eq!(
+        watcher.poll().unwrap(),
+        Some(WatchEvent::Restarted {
+            old_pid: old.pid,
+            new_pid: new.pid,
+            old_base_address: old.base_address,
+            new_base_address: new.base_address,
+        })
+    );
+}
+
+#[test]
+fn detects_reused_pid() {
+    let fixture = Fixture::new("watch-pid-reuse");
+    let mut watcher = GameProcessWatcher::with_procfs(fixture.procfs());
+
+    let game = Game::spawn();
+    let path = game.add_to(&fixture);
+    fixture.started(&path, 100);
+    assert!(matches!(
+        watcher.poll().unwrap(),
+        Some(WatchEvent::Attached { .. })
+    ));
+    assert_eq!(watcher.poll().unwrap(), None);
+
+    // Same pid, but a later start time, so a new process
+    fixture.started(&path, 200);
+    assert_eq!(
+        watcher.poll().unwrap(),
+        Some(WatchEvent::Restarted {
+            old_pid: game.pid,
+            new_pid: game.pid,
+            old_base_address: game.base_address,
+            new_base_address: game.base_address,
+        })
+    );
+    assert_eq!(watcher.poll().unwrap(), None);
+}
+
+#[test]
+fn retries_failed_attach() {
+    let fixture = Fixture::new("watch-retry");
+    let mut watcher = GameProcessWatcher::with_procfs(fixture.procfs());
+
+    let game = Game::spawn();
+    let path = game.add_to(&fixture);
+    let maps = read_to_string(path.join("maps")).unwrap();
+
+    // e.g. read while the game was still being mapped in
+    write(path.join("maps"), "not a mapping\n").unwrap();
+    assert_eq!(watcher.poll().unwrap(), None);
+    assert!(matches!(
+        watcher.attach_error(),
+        Some(Error::InvalidMemoryMap { .. })
+    ));
+
+    write(path.join("maps"), maps).unwrap();
+    assert!(matches!(
+        watcher.poll().unwrap(),
+        Some(WatchEvent::Attached { .. })
+    ));
+    assert!(watcher.attach_error().is_none());
+}
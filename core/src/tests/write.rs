@@ -0,0 +1,35 @@
+use super::own_process;
+use crate::{
+    error::Error,
+    write::{FieldValue, ProcessMemoryWrite},
+};
+
+#[test]
+fn writes_own_memory() {
+    let process = own_process();
+    let value = std::hint::black_box(5u32);
+
+    (&process)
+        .write_mem(&value as *const u32 as usize, &7u32.to_ne_bytes())
+        .unwrap();
+
+    assert_eq!(unsafe { std::ptr::read_volatile(&value) }, 7);
+}
+
+#[test]
+fn rejects_values_wider_than_field() {
+    assert_eq!(i16::narrow("hp", 1).unwrap(), 1);
+    assert_eq!(u32::narrow("gold", u32::MAX as i64).unwrap(), u32::MAX);
+
+    assert!(matches!(
+        i16::narrow("hp", 40000),
+        Err(Error::ValueOutOfRange {
+            field: "hp",
+            value: 40000,
+            min: -32768,
+            max: 32767
+        })
+    ));
+    assert!(u8::narrow("str", -1).is_err());
+    assert!(u32::narrow("gold", -5).is_err());
+}
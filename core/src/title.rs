@@ -0,0 +1,112 @@
+use std::{fmt::Display, path::Path, str::FromStr};
+
+use serde::Deserialize;
+
+use crate::{error::Error, ids::IdsTables};
+
+/// Infinity Engine game a process is running
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum GameTitle {
+    /// Baldur's Gate: Enhanced Edition
+    #[serde(rename = "BGEE")]
+    Bgee,
+    /// BGEE with the Siege of Dragonspear expansion installed
+    #[serde(rename = "SoD")]
+    SoD,
+    /// Baldur's Gate II: Enhanced Edition
+    #[serde(rename = "BG2EE")]
+    Bg2ee,
+    /// BG2EE with the Enhanced Edition Trilogy mod installed
+    #[serde(rename = "EET")]
+    Eet,
+    /// Icewind Dale: Enhanced Edition
+    #[serde(rename = "IWDEE")]
+    Iwdee,
+    /// Planescape: Torment: Enhanced Edition
+    #[serde(rename = "PSTEE")]
+    Pstee,
+}
+impl GameTitle {
+    pub const ALL: [GameTitle; 6] = [
+        Self::Bgee,
+        Self::SoD,
+        Self::Bg2ee,
+        Self::Eet,
+        Self::Iwdee,
+        Self::Pstee,
+    ];
+
+    /// Executable names, which are also the `comm` of the process. Some builds add a `64` suffix
+    const EXECUTABLES: &[(&str, GameTitle)] = &[
+        ("BaldursGate", Self::Bgee),
+        ("BaldursGateII", Self::Bg2ee),
+        ("IcewindDale", Self::Iwdee),
+        ("Torment", Self::Pstee),
+    ];
+
+    /// Title started by the executable `name`, without telling DLC and mods apart
+    pub fn from_executable_name(name: &str) -> Option<Self> {
+        let name = name.strip_suffix("64").unwrap_or(name);
+
+        Self::EXECUTABLES
+            .iter()
+            .find(|(x, _)| *x == name)
+            .map(|(_, title)| *title)
+    }
+
+    /// Title started by the executable `name`, looking in `game_dir` for Siege of Dragonspear
+    /// and the Enhanced Edition Trilogy
+    pub fn detect(name: &str, game_dir: &Path) -> Option<Self> {
+        let title = Self::from_executable_name(name)?;
+
+        let has_sod = || {
+            game_dir.join("dlc").join("sod-dlc.zip").is_file()
+                || game_dir.join("sod-dlc.zip").is_file()
+        };
+
+        Some(match title {
+            Self::Bgee if has_sod() => Self::SoD,
+            Self::Bg2ee if game_dir.join("EET").is_dir() => Self::Eet,
+            x => x,
+        })
+    }
+
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            Self::Bgee => "BGEE",
+            Self::SoD => "SoD",
+            Self::Bg2ee => "BG2EE",
+            Self::Eet => "EET",
+            Self::Iwdee => "IWDEE",
+            Self::Pstee => "PSTEE",
+        }
+    }
+
+    /// IDS tables used to decode this title's creatures
+    pub fn ids(&self) -> IdsTables {
+        match self {
+            Self::Bgee | Self::SoD | Self::Bg2ee | Self::Eet => IdsTables::BaldursGate,
+            Self::Iwdee => IdsTables::IcewindDale,
+            Self::Pstee => IdsTables::Torment,
+        }
+    }
+}
+impl Display for GameTitle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.abbreviation())
+    }
+}
+impl FromStr for GameTitle {
+    type Err = Error;
+
+    /// Case insensitive abbreviation, e.g. `bg2ee`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|x| x.abbreviation().eq_ignore_ascii_case(s))
+            .ok_or_else(|| Error::InvalidEnumValue {
+                enum_type: "GameTitle",
+                value: s.into(),
+            })
+    }
+}
@@ -1,133 +1,84 @@
-use std::ffi::{CStr, c_char, c_void};
+use std::ffi::c_void;
 
 use crate::{
     EntityPtr,
-    error::Error,
+    error::{Context, Error, ResultExt},
     ids::{
+        IdsTables,
         alignment::Alignment,
         classes::{Class, ClassLevels},
         effect::Effect,
-        enemy_ally::EnemyAlly,
+        enemy_ally::{EnemyAlly, Hostility},
         gender::Gender,
         general::General,
+        proficiency::Proficiency,
         race::Race,
+        school::School,
+        secondary_type::SecondaryType,
+        state::State,
     },
+    language::Language,
+    layout::Layout,
+    pod::Pod,
     process::ProcessMemory,
     remote_ptr::RemotePtr,
+    remote_struct::{ReadContext, RemoteStruct},
+    snapshot::View,
 };
 
-#[repr(u8)]
-#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
-pub enum ObjectType {
-    #[default]
-    None = 0x00,
-    AiBase = 0x01,
-    Sound = 0x10,
-    Container = 0x11,
-    Spawning = 0x20,
-    Door = 0x21,
-    Static = 0x30,
-    Sprite = 0x31,
-    ObjectMarker = 0x40,
-    Trigger = 0x41,
-    TiledObject = 0x51,
-    Temporal = 0x60,
-    AreaAi = 0x61,
-    Fireball = 0x70,
-    GameAi = 0x71,
+crate::int_enum! {
+    pub enum ObjectType: u8 {
+        None = 0x00,
+        AiBase = 0x01,
+        Sound = 0x10,
+        Container = 0x11,
+        Spawning = 0x20,
+        Door = 0x21,
+        Static = 0x30,
+        Sprite = 0x31,
+        ObjectMarker = 0x40,
+        Trigger = 0x41,
+        TiledObject = 0x51,
+        Temporal = 0x60,
+        AreaAi = 0x61,
+        Fireball = 0x70,
+        GameAi = 0x71,
+    }
 }
 
 #[repr(C)]
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy)]
 pub struct CPoint {
     pub x: i32,
     pub y: i32,
 }
+unsafe impl Pod for CPoint {}
 
-fn read<T>(process: impl ProcessMemory, ptr: RemotePtr<c_void>, offset: isize) -> Result<T, Error> {
-    unsafe { ptr.byte_offset(offset).cast().read(process) }
-}
-
-fn read_bytes(
-    process: impl ProcessMemory,
-    ptr: RemotePtr<c_void>,
-    offset: isize,
-    len: usize,
-) -> Result<Vec<u8>, Error> {
-    unsafe { ptr.byte_offset(offset).read_bytes(process, len) }
-}
-
-fn read_array<T>(
-    process: impl ProcessMemory,
-    ptr: RemotePtr<c_void>,
-    offset: isize,
-    len: usize,
-) -> Result<Vec<T>, Error> {
-    unsafe { ptr.byte_offset(offset).cast().read_array(process, len) }
+#[derive(Debug, PartialEq, Eq, Clone, PartialOrd, Ord)]
+pub enum Lookup<T, U> {
+    Found(T),
+    Unknown(U),
 }
-
-fn read_res_ref(
-    process: impl ProcessMemory,
-    ptr: RemotePtr<c_void>,
-    offset: isize,
-) -> Result<String, Error> {
-    read_res_ref_with_size(process, ptr, offset, 8)
+impl<T: TryFrom<U>, U: Copy> Lookup<T, U> {
+    /// `Found` if `value` is one of `T`, keeping the raw value otherwise
+    pub fn decode(value: U) -> Self {
+        T::try_from(value).map_or(Self::Unknown(value), Self::Found)
+    }
 }
-
-fn read_res_ref_with_size(
-    process: impl ProcessMemory,
-    ptr: RemotePtr<c_void>,
-    offset: isize,
-    size: usize,
-) -> Result<String, Error> {
-    let bytes = read_bytes(process, ptr, offset, size)?;
-
-    let null_byte = bytes
-        .iter()
-        .enumerate()
-        .find_map(|(x, c)| if *c == b'\0' { Some(x) } else { None });
-
-    match null_byte {
-        Some(end) => {
-            let cs =
-                CStr::from_bytes_with_nul(&bytes[..=end]).map_err(|e| Error::InvalidString {
-                    msg: e.to_string(),
-                    bytes: bytes.clone(),
-                })?;
-            Ok(cs.to_string_lossy().to_string())
-        }
-        None => {
-            let s = str::from_utf8(&bytes).map_err(|e| Error::InvalidString {
-                msg: e.to_string(),
-                bytes: bytes.clone(),
-            })?;
-
-            Ok(s.to_string())
+impl<T: Copy + Into<U>, U: Copy> Lookup<T, U> {
+    /// Value as stored in memory, whether or not it was decoded
+    pub fn raw(&self) -> U {
+        match self {
+            Self::Found(x) => (*x).into(),
+            Self::Unknown(x) => *x,
         }
     }
 }
-
-fn read_string(
-    process: impl ProcessMemory + Copy,
-    ptr: RemotePtr<c_void>,
-    offset: isize,
-    strlen: usize,
-) -> Result<Option<String>, Error> {
-    let char_ptr: *mut c_char = read(process, ptr, offset)?;
-    let char_ptr = RemotePtr::new(char_ptr);
-    let bytes = unsafe { char_ptr.read_bytes(process, strlen)? };
-
-    Ok({
-        CStr::from_bytes_until_nul(&bytes)
-            .ok()
-            .map(|slice| slice.to_str().unwrap().to_string())
-    })
-}
-
-#[derive(Debug, PartialEq, Eq, Clone, PartialOrd, Ord)]
-pub enum Lookup<T, U> {
-    Found(T),
-    Unknown(U),
+/// `Unknown(0)`, e.g. for a structure which hasn't been read yet
+impl<T, U: Default> Default for Lookup<T, U> {
+    fn default() -> Self {
+        Self::Unknown(U::default())
+    }
 }
 impl<T, U> Lookup<T, U> {
     pub fn to_option(self) -> Option<T> {
@@ -146,321 +97,560 @@ impl<T, U> Lookup<T, U> {
 }
 
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, Default, RemoteStruct)]
+#[remote(root = ai_object_type, size = 0x18)]
 /// https://eeex-docs.readthedocs.io/en/latest/EE%20Game%20Structures%20%28x64%29/CA/index.html#caiobjecttype
 pub struct CAIObjectType {
+    #[remote(offset = 0x0, c_string = 8)]
     pub name: Option<String>,
+    #[remote(offset = 0x8, ids = Ea)]
     pub enemy_ally: Lookup<EnemyAlly, u8>,
+    #[remote(offset = 0x9, ids = General)]
     pub general: Lookup<General, u8>,
+    #[remote(offset = 0xA, ids = Race)]
     pub race: Lookup<Race, u8>,
+    #[remote(offset = 0xB, ids = Class)]
     pub class: Lookup<Class, u8>,
+    #[remote(offset = 0xC)]
     pub instance: i32,
+    #[remote(offset = 0x10)]
     pub special_case: [u8; 5],
+    #[remote(offset = 0x15)]
     pub specifics: u8,
+    #[remote(offset = 0x16, ids = Gender)]
     pub gender: Lookup<Gender, u8>,
+    #[remote(offset = 0x17, ids = Align)]
     pub alignment: Lookup<Alignment, u8>,
 }
 impl CAIObjectType {
-    fn new(process: impl ProcessMemory + Copy, ptr: RemotePtr<c_void>) -> Result<Self, Error> {
-        let name = read_string(process, ptr, 0x0, 8)?;
-
-        macro_rules! to_lookup {
-            ($enum_type: ty, $offset: expr) => {{
-                read(process, ptr, $offset).map(|x| {
-                    <$enum_type>::try_from(x)
-                        .map(Lookup::Found)
-                        .unwrap_or(Lookup::Unknown(x))
-                })
-            }};
-        }
-
-        Ok(Self {
-            name,
-            enemy_ally: to_lookup!(EnemyAlly, 0x8)?,
-            general: to_lookup!(General, 0x9)?,
-            race: to_lookup!(Race, 0xA)?,
-            class: to_lookup!(Class, 0xB)?,
-            instance: read(process, ptr, 0xC)?,
-            special_case: read(process, ptr, 0x10)?,
-            specifics: read(process, ptr, 0x15)?,
-            gender: to_lookup!(Gender, 0x16)?,
-            alignment: to_lookup!(Alignment, 0x17)?,
-        })
+    /// Stance towards the party, also for allegiances EA.IDS doesn't list
+    pub fn hostility(&self) -> Hostility {
+        Hostility::of(self.enemy_ally.raw())
     }
 }
 
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, Default, RemoteStruct)]
+#[remote(name = "CGameAIBase", layout = CGameAIBaseLayout, root = ai_base, size = 0x50)]
 /// https://eeex-docs.readthedocs.io/en/latest/EE%20Game%20Structures%20%28x64%29/CG/index.html#cgameobject
 pub struct CGameObject {
-    pub object_type: ObjectType,
+    #[remote(offset = 0x8, lookup)]
+    pub object_type: Lookup<ObjectType, u8>,
+    #[remote(offset = 0xC)]
     pub pos: CPoint,
+    #[remote(offset = 0x14)]
     pub pos_z: i32,
+    #[remote(offset = 0x28)]
     pub list_type: u8,
+    #[remote(offset = 0x30, nested)]
     pub type_ai: CAIObjectType,
+    #[remote(offset = 0x48)]
     pub id: i32,
+    #[remote(offset = 0x4C)]
     pub can_be_seen: i16,
 }
+impl CGameObject {
+    pub fn is_sprite(&self) -> bool {
+        self.object_type == Lookup::Found(ObjectType::Sprite)
+    }
+}
 
 /// Type docs: https://eeex-docs.readthedocs.io/en/latest/EE%20Game%20Structures%20%28x64%29/CG/index.html#cgameaibase
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct CGameAIBase {
     pub object: CGameObject,
 }
 impl CGameAIBase {
     pub fn new(
         process: impl ProcessMemory + Copy,
+        layout: &Layout,
+        ids: IdsTables,
+        language: Language,
         entity: &EntityPtr,
     ) -> Result<Option<Self>, Error> {
         if !entity.is_valid() {
             return Ok(None);
         }
 
-        Ok(Some(Self {
-            object: CGameObject {
-                object_type: read(process, entity.ptr, 0x8)?,
-                pos: read(process, entity.ptr, 0xC)?,
-                pos_z: read(process, entity.ptr, 0x14)?,
-                list_type: read(process, entity.ptr, 0x28)?,
-                type_ai: CAIObjectType::new(process, entity.ptr.byte_offset(0x30))?,
-                id: read(process, entity.ptr, 0x48)?,
-                can_be_seen: read(process, entity.ptr, 0x4C)?,
-            },
-        }))
+        let ctx = ReadContext {
+            process,
+            layout,
+            ids,
+            language,
+        };
+
+        CGameObject::read(&ctx, entity.ptr)
+            .map(|object| Some(Self { object }))
+            .context(Context::Entity { id: entity.id })
+    }
+
+    pub fn from_view(
+        process: impl ProcessMemory + Copy,
+        layout: &Layout,
+        ids: IdsTables,
+        language: Language,
+        view: View,
+    ) -> Result<Self, Error> {
+        let ctx = ReadContext {
+            process,
+            layout,
+            ids,
+            language,
+        };
+
+        Ok(Self {
+            object: CGameObject::from_view(&ctx, view)?,
+        })
+    }
+}
+
+/// One bit per entry of SPLSTATE.IDS, set by effects such as opcode 328
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SpellStates(pub [u32; 8]);
+unsafe impl Pod for SpellStates {}
+impl SpellStates {
+    pub fn contains(&self, state: u8) -> bool {
+        self.0[state as usize / 32] & (1 << (state % 32)) != 0
+    }
+
+    /// Ids of the states which are set
+    pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+        (0..=u8::MAX).filter(|x| self.contains(*x))
     }
 }
 
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, RemoteStruct)]
+#[remote(root = derived_stats, size = 0x2F4)]
 /// https://eeex-docs.readthedocs.io/en/latest/EE%20Game%20Structures%20%28x64%29/CD/index.html#cderivedstats
 pub struct CDerivedStats {
+    /// Includes states from effects, unlike the creature file header
+    #[remote(offset = 0x0)]
+    pub general_state: State,
+    #[remote(offset = 0x4)]
     pub max_hp: i16,
+    #[remote(offset = 0x6)]
     pub ac: i16,
+    #[remote(offset = 0x10)]
     pub thac0: i16,
 
+    #[remote(offset = 0x8)]
     pub ac_crush_mod: i16,
+    #[remote(offset = 0xA)]
     pub ac_missile_mod: i16,
+    #[remote(offset = 0xC)]
     pub ac_pierce_mod: i16,
+    #[remote(offset = 0xE)]
     pub ac_slash_mod: i16,
 
+    #[remote(offset = 0x12)]
     pub number_of_attacks: i16,
 
+    #[remote(offset = 0x14)]
     pub save_vs_death: i16,
+    #[remote(offset = 0x16)]
     pub save_vs_wands: i16,
+    #[remote(offset = 0x18)]
     pub save_vs_poly: i16,
+    #[remote(offset = 0x1A)]
     pub save_vs_breath: i16,
+    #[remote(offset = 0x1C)]
     pub save_vs_spell: i16,
 
+    #[remote(offset = 0x1E)]
     pub resist_fire: i16,
+    #[remote(offset = 0x20)]
     pub resist_cold: i16,
+    #[remote(offset = 0x22)]
     pub resist_electricity: i16,
+    #[remote(offset = 0x24)]
     pub resist_acid: i16,
+    #[remote(offset = 0x26)]
     pub resist_magic: i16,
+    #[remote(offset = 0x28)]
     pub resist_magic_fire: i16,
+    #[remote(offset = 0x2A)]
     pub resist_magic_cold: i16,
+    #[remote(offset = 0x2C)]
     pub resist_slashing: i16,
+    #[remote(offset = 0x2E)]
     pub resist_crushing: i16,
+    #[remote(offset = 0x30)]
     pub resist_piercing: i16,
+    #[remote(offset = 0x32)]
     pub resist_missile: i16,
 
+    #[remote(offset = 0x34)]
+    pub lore: i16,
+    #[remote(offset = 0x36)]
+    pub lock_picking: i16,
+    #[remote(offset = 0x38)]
+    pub move_silently: i16,
+    #[remote(offset = 0x3A)]
+    pub traps: i16,
+    #[remote(offset = 0x3C)]
+    pub pick_pocket: i16,
+    #[remote(offset = 0x3E)]
+    pub fatigue: i16,
+    #[remote(offset = 0x40)]
+    pub intoxication: i16,
+    #[remote(offset = 0x42)]
+    pub luck: i16,
+    #[remote(offset = 0x44)]
+    pub tracking: i16,
+
+    #[remote(offset = 0x46)]
     pub level1: i16,
+    #[remote(offset = 0x48)]
     pub level2: i16,
+    #[remote(offset = 0x4A)]
     pub level3: i16,
+    #[remote(offset = 0x4C, ids = Gender)]
+    pub sex: Lookup<Gender, u8>,
 
+    #[remote(offset = 0x4E)]
     pub str: i16,
     /// e.g. exceptional strength
+    #[remote(offset = 0x50)]
     pub str_extra: i16,
+    #[remote(offset = 0x56)]
     pub dex: i16,
+    #[remote(offset = 0x58)]
     pub con: i16,
+    #[remote(offset = 0x52)]
     pub int: i16,
+    #[remote(offset = 0x54)]
     pub wis: i16,
+    #[remote(offset = 0x5A)]
     pub chr: i16,
+
+    #[remote(offset = 0x5C)]
+    pub xp_value: i32,
+    #[remote(offset = 0x60)]
+    pub xp: i32,
+    #[remote(offset = 0x64)]
+    pub gold: i32,
+
+    #[remote(offset = 0x68)]
+    pub morale_break: i32,
+    #[remote(offset = 0x6C)]
+    pub morale_recovery_time: i32,
+    #[remote(offset = 0x70)]
+    pub reputation: i32,
+    #[remote(offset = 0x74)]
+    pub hated_race: i32,
+
+    #[remote(offset = 0x78)]
+    pub damage_bonus: i32,
+    /// Percent
+    #[remote(offset = 0x7C)]
+    pub spell_failure_mage: i32,
+    /// Percent
+    #[remote(offset = 0x80)]
+    pub spell_failure_priest: i32,
+    /// Percent
+    #[remote(offset = 0x84)]
+    pub spell_duration_mod_mage: i32,
+    /// Percent
+    #[remote(offset = 0x88)]
+    pub spell_duration_mod_priest: i32,
+    #[remote(offset = 0x8C)]
+    pub turn_undead_level: i32,
+    #[remote(offset = 0x90)]
+    pub backstab_damage_multiplier: i32,
+    #[remote(offset = 0x94)]
+    pub lay_on_hands_amount: i32,
+
+    #[remote(offset = 0x98)]
+    pub held: i32,
+    #[remote(offset = 0x9C)]
+    pub polymorphed: i32,
+    #[remote(offset = 0xA0)]
+    pub translucent: i32,
+    #[remote(offset = 0xA4)]
+    pub identify_mode: i32,
+    #[remote(offset = 0xA8)]
+    pub entangle: i32,
+    #[remote(offset = 0xAC)]
+    pub sanctuary: i32,
+    #[remote(offset = 0xB0)]
+    pub minor_globe: i32,
+    #[remote(offset = 0xB4)]
+    pub shield_globe: i32,
+    #[remote(offset = 0xB8)]
+    pub grease: i32,
+    #[remote(offset = 0xBC)]
+    pub web: i32,
+    #[remote(offset = 0xC0)]
+    pub caster_hold: i32,
+
+    #[remote(offset = 0xC4)]
+    pub encumbrance: i32,
+    #[remote(offset = 0xC8)]
+    pub missile_thac0_bonus: i32,
+    #[remote(offset = 0xCC)]
+    pub magic_damage_resistance: i32,
+    #[remote(offset = 0xD0)]
+    pub resist_poison: i32,
+    #[remote(offset = 0xD4)]
+    pub do_not_jump: i32,
+    #[remote(offset = 0xD8)]
+    pub aura_cleansing: i32,
+    #[remote(offset = 0xDC)]
+    pub mental_speed: i32,
+    #[remote(offset = 0xE0)]
+    pub physical_speed: i32,
+    #[remote(offset = 0xE4)]
+    pub casting_level_bonus_mage: i32,
+    #[remote(offset = 0xE8)]
+    pub casting_level_bonus_cleric: i32,
+    #[remote(offset = 0xEC)]
+    pub see_invisible: i32,
+    #[remote(offset = 0xF0)]
+    pub ignore_dialog_pause: i32,
+    #[remote(offset = 0xF4)]
+    pub min_hp: i32,
+
+    #[remote(offset = 0xF8)]
+    pub thac0_bonus_right: i32,
+    #[remote(offset = 0xFC)]
+    pub thac0_bonus_left: i32,
+    #[remote(offset = 0x100)]
+    pub damage_bonus_right: i32,
+    #[remote(offset = 0x104)]
+    pub damage_bonus_left: i32,
+    #[remote(offset = 0x108)]
+    pub stone_skins: i32,
+
+    /// Indexed from [`Proficiency::FIRST`], see [`Self::proficiency`]
+    #[remote(offset = 0x10C)]
+    pub proficiencies: [i32; Proficiency::COUNT],
+
+    #[remote(offset = 0x174)]
+    pub hide_in_shadows: i32,
+    #[remote(offset = 0x178)]
+    pub detect_illusion: i32,
+    #[remote(offset = 0x17C)]
+    pub set_traps: i32,
+
+    /// Fleeing below [`Self::morale_break`]
+    #[remote(offset = 0x218)]
+    pub morale: i32,
+    #[remote(offset = 0x268)]
+    pub movement_rate: i32,
+    /// Raw immunity flags, whose bits aren't decoded yet
+    #[remote(offset = 0x2D0)]
+    pub immunities: u32,
+    #[remote(offset = 0x2D4)]
+    pub spell_states: SpellStates,
 }
+
 impl CDerivedStats {
-    pub fn new(process: impl ProcessMemory + Copy, ptr: RemotePtr<c_void>) -> Result<Self, Error> {
-        Ok(Self {
-            max_hp: read(process, ptr, 0x4)?,
-            ac: read(process, ptr, 0x6)?,
-            thac0: read(process, ptr, 0x10)?,
-
-            ac_crush_mod: read(process, ptr, 0x8)?,
-            ac_missile_mod: read(process, ptr, 0xA)?,
-            ac_pierce_mod: read(process, ptr, 0xC)?,
-            ac_slash_mod: read(process, ptr, 0xE)?,
-
-            number_of_attacks: read(process, ptr, 0x12)?,
-
-            save_vs_death: read(process, ptr, 0x14)?,
-            save_vs_wands: read(process, ptr, 0x16)?,
-            save_vs_poly: read(process, ptr, 0x18)?,
-            save_vs_breath: read(process, ptr, 0x1A)?,
-            save_vs_spell: read(process, ptr, 0x1C)?,
-
-            resist_fire: read(process, ptr, 0x1E)?,
-            resist_cold: read(process, ptr, 0x20)?,
-            resist_electricity: read(process, ptr, 0x22)?,
-            resist_acid: read(process, ptr, 0x24)?,
-            resist_magic: read(process, ptr, 0x26)?,
-            resist_magic_fire: read(process, ptr, 0x28)?,
-            resist_magic_cold: read(process, ptr, 0x2A)?,
-
-            resist_slashing: read(process, ptr, 0x2C)?,
-            resist_crushing: read(process, ptr, 0x2E)?,
-            resist_piercing: read(process, ptr, 0x30)?,
-            resist_missile: read(process, ptr, 0x32)?,
-
-            level1: read(process, ptr, 0x46)?,
-            level2: read(process, ptr, 0x48)?,
-            level3: read(process, ptr, 0x4A)?,
-
-            str: read(process, ptr, 0x4E)?,
-            str_extra: read(process, ptr, 0x50)?,
-            int: read(process, ptr, 0x52)?,
-            wis: read(process, ptr, 0x54)?,
-            dex: read(process, ptr, 0x56)?,
-            con: read(process, ptr, 0x58)?,
-            chr: read(process, ptr, 0x5A)?,
-        })
+    pub fn proficiency(&self, proficiency: Proficiency) -> i32 {
+        self.proficiencies[(proficiency as u8 - Proficiency::FIRST as u8) as usize]
     }
 }
 
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, RemoteStruct)]
+#[remote(root = creature_file_header, size = 0x238)]
 /// https://eeex-docs.readthedocs.io/en/latest/EE%20Game%20Structures%20%28x64%29/CC/index.html#ccreaturefileheader
 pub struct CCreatureFileHeader {
+    #[remote(offset = 0x14)]
+    pub gold: u32,
+    /// Current hit points, which the engine keeps up to date here rather than in
+    /// [`CDerivedStats`]
+    #[remote(offset = 0x1C)]
     pub hp: i16,
+    #[remote(offset = 0x22C)]
     pub level1: i8,
+    #[remote(offset = 0x22D)]
     pub level2: i8,
+    #[remote(offset = 0x22E)]
     pub level3: i8,
-}
-impl CCreatureFileHeader {
-    pub fn new(process: impl ProcessMemory + Copy, ptr: RemotePtr<c_void>) -> Result<Self, Error> {
-        Ok(Self {
-            hp: read(process, ptr, 0x1C)?,
-            level1: read(process, ptr, 0x22C)?,
-            level2: read(process, ptr, 0x22D)?,
-            level3: read(process, ptr, 0x22E)?,
-        })
-    }
+
+    #[remote(offset = 0x230)]
+    pub str: u8,
+    /// e.g. exceptional strength
+    #[remote(offset = 0x231)]
+    pub str_extra: u8,
+    #[remote(offset = 0x232)]
+    pub int: u8,
+    #[remote(offset = 0x233)]
+    pub wis: u8,
+    #[remote(offset = 0x234)]
+    pub dex: u8,
+    #[remote(offset = 0x235)]
+    pub con: u8,
+    #[remote(offset = 0x236)]
+    pub chr: u8,
 }
 
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, RemoteStruct)]
+#[remote(root = effect, size = 0xD4, base = 0x8)]
+/// https://eeex-docs.readthedocs.io/en/latest/EE%20Game%20Structures%20%28x64%29/CG/index.html#cgameeffect
+///
+/// The fields follow the EFF V2.0 format, see
+/// https://gibberlings3.github.io/iesdp/file_formats/ie_formats/eff_v2.htm
 pub struct CGameEffect {
-    version: String,
-    res: String,
-    res_2: String,
-    res_3: String,
-    script_name: String,
-    effect_id: Effect,
-    duration: u32,
-    duration_type: u32,
-    spell_level: i32,
-    source_res: String,
-}
-impl CGameEffect {
-    pub fn new(process: impl ProcessMemory + Copy, ptr: RemotePtr<c_void>) -> Result<Self, Error> {
-        let base_ptr = ptr.byte_offset(0x8);
-
-        let version = read_res_ref(process, base_ptr, 0x0)?;
-        let res = read_res_ref(process, base_ptr, 0x28)?;
-        let spell_level = read(process, base_ptr, 0x10)?;
-
-        Ok(Self {
-            version,
-            res,
-            res_2: read_res_ref(process, base_ptr, 0x68)?,
-            res_3: read_res_ref(process, base_ptr, 0x70)?,
-            script_name: read_res_ref_with_size(process, base_ptr, 0xA0, 32)?,
-            effect_id: read(process, base_ptr, 0x8)?,
-            duration_type: read(process, base_ptr, 0x1C)?,
-            duration: read(process, base_ptr, 0x20)?,
-            spell_level,
-            source_res: read_res_ref(process, base_ptr, 0x8C)?,
-        })
-    }
-}
-
-fn read_ptr_list<T, P: ProcessMemory + Copy>(
-    process: P,
-    base_ptr: RemotePtr<c_void>,
-    read_func: impl Fn(P, RemotePtr<c_void>) -> Result<T, Error>,
-) -> Result<Vec<T>, Error> {
-    let mut lst = vec![];
-    let mut head: RemotePtr<c_void> = unsafe { base_ptr.byte_offset(0x8).cast().read(process)? };
-
-    let count: u32 = unsafe { base_ptr.byte_offset(0x18).cast().read(process)? };
-
-    for _ in 0..count {
-        let next = unsafe { head.cast().read(process)? };
-
-        let data_ptr: RemotePtr<c_void> = unsafe { head.byte_offset(0x10).cast().read(process)? };
-
-        let x = read_func(process, data_ptr.cast())?;
-        lst.push(x);
-
-        head = next;
-    }
-
-    Ok(lst)
+    #[remote(offset = 0x0, res_ref)]
+    pub version: String,
+    #[remote(offset = 0x8, lookup)]
+    pub effect_id: Lookup<Effect, u32>,
+    #[remote(offset = 0xC)]
+    pub target: u32,
+    /// Power level of the spell which applied the effect
+    #[remote(offset = 0x10)]
+    pub spell_level: i32,
+    #[remote(offset = 0x14)]
+    pub parameter1: i32,
+    #[remote(offset = 0x18)]
+    pub parameter2: i32,
+    #[remote(offset = 0x1C)]
+    pub duration_type: u32,
+    #[remote(offset = 0x20)]
+    pub duration: u32,
+    /// Upper bound of the roll from 0 to 99 under which the effect applies
+    #[remote(offset = 0x24)]
+    pub probability1: u16,
+    /// Lower bound of the roll
+    #[remote(offset = 0x26)]
+    pub probability2: u16,
+    #[remote(offset = 0x28, res_ref)]
+    pub res: String,
+    #[remote(offset = 0x30)]
+    pub dice_thrown: u32,
+    #[remote(offset = 0x34)]
+    pub dice_sides: u32,
+    #[remote(offset = 0x38)]
+    pub saving_throw: u32,
+    #[remote(offset = 0x3C)]
+    pub save_bonus: i32,
+    #[remote(offset = 0x40)]
+    pub special: u32,
+    #[remote(offset = 0x44, lookup)]
+    pub school: Lookup<School, u32>,
+    #[remote(offset = 0x4C)]
+    pub min_level: u32,
+    #[remote(offset = 0x50)]
+    pub max_level: u32,
+    /// Dispel and magic resistance flags
+    #[remote(offset = 0x54)]
+    pub resistance: u32,
+    #[remote(offset = 0x58)]
+    pub parameter3: i32,
+    #[remote(offset = 0x5C)]
+    pub parameter4: i32,
+    #[remote(offset = 0x68, res_ref)]
+    pub res_2: String,
+    #[remote(offset = 0x70, res_ref)]
+    pub res_3: String,
+    /// Spell or item which applied the effect
+    #[remote(offset = 0x8C, res_ref)]
+    pub source_res: String,
+    #[remote(offset = 0xA0, text = 32)]
+    pub script_name: String,
+    #[remote(offset = 0xC0)]
+    pub caster_level: u32,
+    #[remote(offset = 0xC8, lookup)]
+    pub secondary_type: Lookup<SecondaryType, u32>,
 }
 
 #[repr(C)]
-#[derive(Debug)]
+#[derive(Debug, RemoteStruct)]
+#[remote(root = sprite, size = 0x4A10)]
 /// https://eeex-docs.readthedocs.io/en/latest/EE%20Game%20Structures%20%28x64%29/CG/index.html#cgamesprite
 pub struct CGameSprite {
+    #[remote(skip)]
+    pub ptr: RemotePtr<c_void>,
+    /// Read before the sprite, to tell whether the entity is one
+    #[remote(skip)]
     pub base: CGameAIBase,
+    #[remote(offset = 0x540, res_ref)]
     pub res_ref: String,
+    #[remote(offset = 0x560, nested)]
     pub base_stats: CCreatureFileHeader,
-    pub name: String,
+    #[remote(offset = 0x3910, c_string = 64)]
+    pub name: Option<String>,
+    #[remote(offset = 0x1120, nested)]
     pub derived_stats: CDerivedStats,
+    #[remote(offset = 0x3A08, res_ref)]
     pub current_area: String,
 
-    pub class_levels: ClassLevels,
+    /// `None` when the class isn't one of [`Class`]
+    #[remote(skip)]
+    pub class_levels: Option<ClassLevels>,
+    #[remote(offset = 0x4998, list)]
     pub equipped_effects: Vec<CGameEffect>,
+    #[remote(offset = 0x49E8, list)]
     pub timed_effects: Vec<CGameEffect>,
+    /// Why effects missing from the lists above couldn't be read
+    #[remote(errors)]
+    pub effect_errors: Vec<Error>,
 }
 impl CGameSprite {
     pub fn new(
         process: impl ProcessMemory + Copy,
+        layout: &Layout,
+        ids: IdsTables,
+        language: Language,
         entity @ EntityPtr { ptr, .. }: &EntityPtr,
         base: CGameAIBase,
     ) -> Result<Option<Self>, Error> {
-        if !entity.is_valid() || base.object.object_type != ObjectType::Sprite {
-            Ok(None)
-        } else {
-            let res_ref = read_res_ref(process, *ptr, 0x540)?;
-
-            let derived_stats = CDerivedStats::new(process, ptr.byte_offset(0x1120))?;
-            let class = base.object.type_ai.class.clone().to_option().unwrap();
-            let levels = class.get_levels(&derived_stats);
-
-            // 0x18 before value in docs?
-            let name = read_string(process, *ptr, 0x3910, 64)?.unwrap();
-            let current_area = read_res_ref(process, entity.ptr, 0x3A20 - 0x18)?;
-
-            let equipped_effects = {
-                let offset = ptr.byte_offset(0x49B0 - 0x18);
-                read_ptr_list(process, offset, CGameEffect::new)
-            }?;
-
-            let timed_effects = {
-                let offset = ptr.byte_offset(0x4A00 - 0x18);
-                read_ptr_list(process, offset, CGameEffect::new)
-            }?;
-
-            Ok(Some(Self {
-                base,
-                res_ref,
-                base_stats: CCreatureFileHeader::new(process, ptr.byte_offset(0x560))?,
-                name,
-                derived_stats,
-                current_area,
-                class_levels: levels,
-                equipped_effects,
-                timed_effects,
-            }))
+        if !entity.is_valid() || !base.object.is_sprite() {
+            return Ok(None);
         }
+
+        let ctx = ReadContext {
+            process,
+            layout,
+            ids,
+            language,
+        };
+
+        let mut sprite = Self::read(&ctx, *ptr).context(Context::Entity { id: entity.id })?;
+        sprite.class_levels = base
+            .object
+            .type_ai
+            .class
+            .clone()
+            .to_option()
+            .map(|class| class.get_levels(&sprite.derived_stats));
+        sprite.ptr = *ptr;
+        sprite.base = base;
+
+        Ok(Some(sprite))
+    }
+
+    /// Current hit points
+    pub fn hp(&self) -> i16 {
+        self.base_stats.hp
+    }
+
+    /// Maximum hit points, including effects
+    pub fn max_hp(&self) -> i16 {
+        self.derived_stats.max_hp
+    }
+
+    /// Current hit points out of the maximum, from 0 to 1. `None` when the maximum isn't positive,
+    /// e.g. for a sprite which hasn't finished loading
+    pub fn hp_fraction(&self) -> Option<f32> {
+        (self.max_hp() > 0).then(|| (self.hp() as f32 / self.max_hp() as f32).clamp(0.0, 1.0))
+    }
+
+    pub fn state(&self) -> State {
+        self.derived_stats.general_state
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.state().is_dead()
+    }
+
+    /// Whether the sprite can't act on its own, e.g. asleep, stunned or dead
+    pub fn is_disabled(&self) -> bool {
+        self.state().is_disabled()
     }
 }
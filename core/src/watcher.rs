@@ -0,0 +1,144 @@
+use std::{
+    num::{NonZeroU32, NonZeroUsize},
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    error::Error,
+    process::GameProcess,
+    procfs::{GameSelector, Procfs, start_time},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WatchEvent {
+    /// The game was found for the first time since the watcher was created
+    Attached {
+        pid: NonZeroU32,
+        base_address: NonZeroUsize,
+    },
+    /// The attached game exited and no replacement has been found yet
+    Detached { pid: NonZeroU32 },
+    /// A game was found again after a previous one exited
+    Restarted {
+        old_pid: NonZeroU32,
+        new_pid: NonZeroU32,
+        old_base_address: NonZeroUsize,
+        new_base_address: NonZeroUsize,
+    },
+}
+
+/// Polls `/proc` for the game and keeps track of it across game restarts.
+///
+/// Consumers call [`GameProcessWatcher::poll`] (or [`GameProcessWatcher::wait`]) whenever a read
+/// fails with [`Error::GameProcessClosed`], then carry on reading from
+/// [`GameProcessWatcher::process`].
+#[derive(Debug, Default)]
+pub struct GameProcessWatcher {
+    procfs: Procfs,
+    selector: GameSelector,
+    process: Option<GameProcess>,
+    /// Start time of `process`, so a new process reusing its pid isn't mistaken for it
+    started: Option<SystemTime>,
+    /// pid and base address of the last process we were attached to
+    previous: Option<(NonZeroU32, NonZeroUsize)>,
+    /// Why the last scan couldn't attach, if it found a game but failed
+    attach_error: Option<Error>,
+}
+impl GameProcessWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Watches the processes in `procfs` rather than `/proc`
+    pub fn with_procfs(procfs: Procfs) -> Self {
+        Self {
+            procfs,
+            ..Self::default()
+        }
+    }
+
+    /// Only attaches to games picked by `selector`, e.g. BG2EE while BGEE is also running
+    pub fn with_selector(self, selector: GameSelector) -> Self {
+        Self { selector, ..self }
+    }
+
+    pub fn process(&self) -> Option<&GameProcess> {
+        self.process.as_ref()
+    }
+
+    pub fn is_attached(&self) -> bool {
+        self.process.is_some()
+    }
+
+    /// Why the last scan couldn't attach to a game. Such failures are often transient, e.g. a
+    /// game still starting up, so the watcher keeps retrying rather than returning them
+    pub fn attach_error(&self) -> Option<&Error> {
+        self.attach_error.as_ref()
+    }
+
+    /// Checks the attached process is still alive, or scans for a new one if not.
+    ///
+    /// Returns `Ok(None)` when nothing changed since the last poll.
+    pub fn poll(&mut self) -> Result<Option<WatchEvent>, Error> {
+        if let Some(process) = &self.process {
+            if process.exists() && start_time(&process.path) == self.started {
+                return Ok(None);
+            }
+
+            self.previous = Some((process.pid, process.base_address));
+            self.process = None;
+
+            // The game may already have been relaunched between polls
+            return match self.scan()? {
+                Some(event) => Ok(Some(event)),
+                None => Ok(self.previous.map(|(pid, _)| WatchEvent::Detached { pid })),
+            };
+        }
+
+        self.scan()
+    }
+
+    /// Blocks until the next event, polling every `interval`
+    pub fn wait(&mut self, interval: Duration) -> Result<WatchEvent, Error> {
+        loop {
+            if let Some(event) = self.poll()? {
+                return Ok(event);
+            }
+
+            std::thread::sleep(interval);
+        }
+    }
+
+    fn scan(&mut self) -> Result<Option<WatchEvent>, Error> {
+        let process = match self.procfs.find_game_process_by(&self.selector, true) {
+            Ok(process) => process,
+            Err(Error::MissingGameProcess) => {
+                self.attach_error = None;
+                return Ok(None);
+            }
+            Err(e) => {
+                self.attach_error = Some(e);
+                return Ok(None);
+            }
+        };
+
+        let event = match self.previous {
+            Some((old_pid, old_base_address)) => WatchEvent::Restarted {
+                old_pid,
+                new_pid: process.pid,
+                old_base_address,
+                new_base_address: process.base_address,
+            },
+            None => WatchEvent::Attached {
+                pid: process.pid,
+                base_address: process.base_address,
+            },
+        };
+
+        self.started = start_time(&process.path);
+        self.process = Some(process);
+        self.attach_error = None;
+
+        Ok(Some(event))
+    }
+}
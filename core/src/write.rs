@@ -0,0 +1,170 @@
+use crate::{
+    error::Error,
+    layout::Layout,
+    process::GameProcess,
+    types::{CCreatureFileHeader, CGameSprite},
+};
+
+pub trait ProcessMemoryWrite {
+    fn write_mem(&self, address: usize, bytes: &[u8]) -> Result<(), Error>;
+}
+
+impl ProcessMemoryWrite for &GameProcess {
+    /// Always `process_vm_writev`, whichever backend is used for reads
+    fn write_mem(&self, address: usize, bytes: &[u8]) -> Result<(), Error> {
+        let local_iovec = libc::iovec {
+            iov_len: bytes.len(),
+            iov_base: bytes.as_ptr() as *mut libc::c_void,
+        };
+
+        let remote_iovec = libc::iovec {
+            iov_len: bytes.len(),
+            iov_base: address as *mut libc::c_void,
+        };
+
+        let written = unsafe {
+            libc::process_vm_writev(self.pid.get() as i32, &local_iovec, 1, &remote_iovec, 1, 0)
+        };
+
+        if written < 0 {
+            Err(std::io::Error::last_os_error().into())
+        } else if written as usize != bytes.len() {
+            Err(Error::Memory(format!(
+                "Partial write at 0x{address:x}: wrote {written} of {} bytes",
+                bytes.len()
+            )))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Integer types fields can be written as, checking values fit before writing anything
+pub(crate) trait FieldValue: Sized + Copy {
+    fn narrow(field: &'static str, value: i64) -> Result<Self, Error>;
+    fn to_bytes(self) -> Vec<u8>;
+}
+
+macro_rules! field_value {
+    ($($type: ty),+) => {
+        $(
+            impl FieldValue for $type {
+                fn narrow(field: &'static str, value: i64) -> Result<Self, Error> {
+                    Self::try_from(value).map_err(|_| Error::ValueOutOfRange {
+                        field,
+                        value,
+                        min: Self::MIN as i64,
+                        max: Self::MAX as i64,
+                    })
+                }
+
+                fn to_bytes(self) -> Vec<u8> {
+                    self.to_ne_bytes().to_vec()
+                }
+            }
+        )+
+    };
+}
+field_value!(i8, u8, i16, u16, i32, u32);
+
+/// Base abilities of a [`CCreatureFileHeader`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaseStat {
+    Str,
+    StrExtra,
+    Int,
+    Wis,
+    Dex,
+    Con,
+    Chr,
+}
+impl BaseStat {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Str => "str",
+            Self::StrExtra => "str_extra",
+            Self::Int => "int",
+            Self::Wis => "wis",
+            Self::Dex => "dex",
+            Self::Con => "con",
+            Self::Chr => "chr",
+        }
+    }
+
+    fn offset(&self, layout: &Layout) -> isize {
+        let header = &layout.creature_file_header;
+
+        match self {
+            Self::Str => header.str,
+            Self::StrExtra => header.str_extra,
+            Self::Int => header.int,
+            Self::Wis => header.wis,
+            Self::Dex => header.dex,
+            Self::Con => header.con,
+            Self::Chr => header.chr,
+        }
+    }
+
+    fn field<'a>(&self, header: &'a mut CCreatureFileHeader) -> &'a mut u8 {
+        match self {
+            Self::Str => &mut header.str,
+            Self::StrExtra => &mut header.str_extra,
+            Self::Int => &mut header.int,
+            Self::Wis => &mut header.wis,
+            Self::Dex => &mut header.dex,
+            Self::Con => &mut header.con,
+            Self::Chr => &mut header.chr,
+        }
+    }
+}
+
+/// Setters write to the game and then update the local copy to match, at the offsets of the
+/// layout `process` was attached with. The game recalculates derived stats from these on its own
+/// schedule
+impl CGameSprite {
+    fn write_base_stats_field<T: FieldValue>(
+        &self,
+        process: &GameProcess,
+        field: &'static str,
+        offset: isize,
+        value: i64,
+    ) -> Result<T, Error> {
+        let value = T::narrow(field, value)?;
+
+        let address = self
+            .ptr
+            .byte_offset(process.layout.sprite.base_stats + offset)
+            .addr();
+        process.write_mem(address, &value.to_bytes())?;
+
+        Ok(value)
+    }
+
+    /// Current hit points, e.g. 1 to leave a creature on the brink of death
+    pub fn set_hp(&mut self, process: &GameProcess, hp: i64) -> Result<(), Error> {
+        let offset = process.layout.creature_file_header.hp;
+        self.base_stats.hp = self.write_base_stats_field(process, "hp", offset, hp)?;
+
+        Ok(())
+    }
+
+    pub fn set_gold(&mut self, process: &GameProcess, gold: i64) -> Result<(), Error> {
+        let offset = process.layout.creature_file_header.gold;
+        self.base_stats.gold = self.write_base_stats_field(process, "gold", offset, gold)?;
+
+        Ok(())
+    }
+
+    pub fn set_base_stat(
+        &mut self,
+        process: &GameProcess,
+        stat: BaseStat,
+        value: i64,
+    ) -> Result<(), Error> {
+        let offset = stat.offset(&process.layout);
+        let value = self.write_base_stats_field(process, stat.name(), offset, value)?;
+        *stat.field(&mut self.base_stats) = value;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,445 @@
+//! Derive macros for the core crate, see `RemoteStruct` there
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    Data, DeriveInput, Fields, GenericArgument, Ident, LitInt, LitStr, PathArguments, Type,
+    parse_macro_input, spanned::Spanned,
+};
+
+/// Implements `RemoteStruct` and generates the layout structure holding its offsets.
+///
+/// On the structure:
+/// - `size = 0x..`: bytes read per snapshot (required)
+/// - `root = field`: field of `Layout` holding the offsets (required)
+/// - `name = ".."`: name in the game and in layout files, defaults to the structure's
+/// - `layout = Ident`: name of the generated layout, defaults to `<Struct>Layout`
+/// - `base = 0x..`: start of an embedded base class which field offsets are relative to
+///
+/// On each field, `offset = 0x..` plus at most one of:
+/// - nothing: read as is, so the type must be `Pod`
+/// - `lookup`: a `Lookup<T, U>` decoded from a `U`
+/// - `ids = File`: a `Lookup<T, u8>` decoded with the `IdsFile::File` table of the title
+/// - `res_ref`, `text = N`: fixed size text, 8 or `N` bytes
+/// - `c_string = N`: pointer to text of at most `N` bytes
+/// - `nested`: another `RemoteStruct` embedded at the offset
+/// - `list`: a `Vec<T>` read from the `CPtrList` of pointers to `T` at the offset
+///
+/// or, without an offset:
+/// - `errors`: a `Vec<Error>` collecting elements of `list` fields which couldn't be read
+/// - `skip`: not read, left as `Default::default()`
+#[proc_macro_derive(RemoteStruct, attributes(remote))]
+pub fn derive_remote_struct(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct StructAttrs {
+    name: Option<LitStr>,
+    layout: Option<Ident>,
+    root: Option<Ident>,
+    size: Option<LitInt>,
+    base: Option<LitInt>,
+}
+
+enum Kind {
+    Value,
+    Lookup(Type),
+    Ids(Ident),
+    ResRef,
+    Text(LitInt),
+    CString(LitInt),
+    Nested,
+    List(Type),
+    Errors,
+    Skip,
+}
+
+struct Field {
+    ident: Ident,
+    ty: Type,
+    offset: Option<LitInt>,
+    kind: Kind,
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let vis = &input.vis;
+
+    let mut attrs = StructAttrs::default();
+    for attr in input.attrs.iter().filter(|x| x.path().is_ident("remote")) {
+        attr.parse_nested_meta(|meta| {
+            let value = meta.value()?;
+
+            if meta.path.is_ident("name") {
+                attrs.name = Some(value.parse()?);
+            } else if meta.path.is_ident("layout") {
+                attrs.layout = Some(value.parse()?);
+            } else if meta.path.is_ident("root") {
+                attrs.root = Some(value.parse()?);
+            } else if meta.path.is_ident("size") {
+                attrs.size = Some(value.parse()?);
+            } else if meta.path.is_ident("base") {
+                attrs.base = Some(value.parse()?);
+            } else {
+                return Err(meta.error("unknown remote attribute"));
+            }
+
+            Ok(())
+        })?;
+    }
+
+    let missing = |key: &str| {
+        syn::Error::new(
+            input.ident.span(),
+            format!("missing #[remote({key} = ..)] on the structure"),
+        )
+    };
+    let size = attrs.size.ok_or_else(|| missing("size"))?;
+    let root = attrs.root.ok_or_else(|| missing("root"))?;
+    let name = attrs
+        .name
+        .unwrap_or_else(|| LitStr::new(&ident.to_string(), ident.span()));
+    let layout = attrs
+        .layout
+        .unwrap_or_else(|| format_ident!("{}Layout", ident));
+
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.span(),
+            "RemoteStruct only supports structures",
+        ));
+    };
+    let Fields::Named(named) = &data.fields else {
+        return Err(syn::Error::new(
+            input.span(),
+            "RemoteStruct only supports named fields",
+        ));
+    };
+
+    let fields = named
+        .named
+        .iter()
+        .map(parse_field)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let read_fields = fields.iter().filter(|x| x.offset.is_some());
+    let field_idents = read_fields.clone().map(|x| &x.ident).collect::<Vec<_>>();
+    let offsets = read_fields
+        .clone()
+        .map(|x| x.offset.as_ref().unwrap())
+        .collect::<Vec<_>>();
+
+    let (base_field, base_default, base_offset, base_value) = match &attrs.base {
+        Some(base) => (
+            quote! {
+                /// Start of the embedded base class, which the fields below are relative to
+                pub base: isize,
+            },
+            quote!(base: #base,),
+            quote!(layout.base +),
+            quote!(#base),
+        ),
+        None => (quote!(), quote!(), quote!(), quote!(0)),
+    };
+
+    let field_infos = read_fields
+        .clone()
+        .map(|field| {
+            let field_name = field.ident.to_string();
+            let offset = field.offset.as_ref().unwrap();
+            let size = field_size(field);
+            let kind = match &field.kind {
+                Kind::Value => quote!(Value),
+                Kind::Lookup(_) => quote!(Lookup),
+                Kind::Ids(_) => quote!(Ids),
+                Kind::ResRef => quote!(Text),
+                Kind::Text(_) => quote!(Text),
+                Kind::CString(_) => quote!(CString),
+                Kind::Nested => {
+                    let ty = &field.ty;
+                    quote!(Nested(<#ty as crate::remote_struct::RemoteStruct>::NAME))
+                }
+                Kind::List(ty) => {
+                    quote!(List(<#ty as crate::remote_struct::RemoteStruct>::NAME))
+                }
+                Kind::Errors | Kind::Skip => unreachable!(),
+            };
+
+            quote! {
+                crate::remote_struct::FieldInfo {
+                    name: #field_name,
+                    offset: #base_value + #offset,
+                    size: #size,
+                    kind: crate::remote_struct::FieldKind::#kind,
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let assertions = read_fields.clone().map(|field| {
+        let offset = field.offset.as_ref().unwrap();
+        let field_size = field_size(field);
+        let message = format!("{}.{} is outside of its size", name.value(), field.ident);
+
+        quote! {
+            // Fields one byte wide end up as `x + 1 <= size`
+            #[allow(clippy::int_plus_one)]
+            const _: () = assert!(
+                (#base_value + #offset) as usize + #field_size <= #size,
+                #message
+            );
+        }
+    });
+
+    let has_lists = fields.iter().any(|x| matches!(x.kind, Kind::List(_)));
+    let has_errors = fields.iter().any(|x| matches!(x.kind, Kind::Errors));
+    if has_lists && !has_errors {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "structures with list fields need an #[remote(errors)] field",
+        ));
+    }
+
+    let decode = fields.iter().map(|field| {
+        let field_ident = &field.ident;
+        let field_name = field_ident.to_string();
+        let offset = quote!(#base_offset layout.#field_ident);
+
+        let value = match &field.kind {
+            Kind::Value => quote!(view.field(#field_name, #offset)?),
+            Kind::Lookup(_) => {
+                quote!(view.field(#field_name, #offset).map(crate::types::Lookup::decode)?)
+            }
+            Kind::Ids(file) => quote! {
+                view.field(#field_name, #offset)
+                    .map(|x| ctx.ids.lookup(crate::ids::IdsFile::#file, x))?
+            },
+            Kind::ResRef => quote! {
+                crate::remote_struct::read_text(view, ctx.language, #field_name, #offset, 8)?
+            },
+            Kind::Text(size) => quote! {
+                crate::remote_struct::read_text(view, ctx.language, #field_name, #offset, #size)?
+            },
+            Kind::CString(size) => quote! {
+                crate::error::ResultExt::context(
+                    crate::remote_struct::read_c_string(
+                        ctx.process,
+                        ctx.language,
+                        view.field(#field_name, #offset)?,
+                        #size,
+                    ),
+                    view.context(#field_name, #offset),
+                )?
+            },
+            Kind::Nested => {
+                let ty = &field.ty;
+                quote! {
+                    <#ty as crate::remote_struct::RemoteStruct>::from_view(
+                        ctx,
+                        view.field_at(#field_name, #offset)?,
+                    )?
+                }
+            }
+            Kind::List(_) => quote! {{
+                let (items, list_errors) =
+                    crate::remote_struct::read_list(ctx, view, #field_name, #offset)?;
+                errors.extend(list_errors);
+                items
+            }},
+            // Once every list has been read
+            Kind::Errors => return quote!(),
+            Kind::Skip => quote!(Default::default()),
+        };
+
+        quote!(let #field_ident = #value;)
+    });
+
+    let errors_field = fields
+        .iter()
+        .find(|x| matches!(x.kind, Kind::Errors))
+        .map(|x| {
+            let field_ident = &x.ident;
+            quote!(let #field_ident = errors;)
+        });
+    let errors_init = has_errors.then(|| quote!(let mut errors = Vec::new();));
+
+    let all_idents = fields.iter().map(|x| &x.ident);
+    let layout_doc = format!(
+        "Offsets of [`{ident}`], each defaulting to its `#[remote(offset)]` when a layout file \
+         leaves it out"
+    );
+
+    Ok(quote! {
+        #[doc = #layout_doc]
+        #[derive(Debug, Clone, serde::Deserialize)]
+        #[serde(deny_unknown_fields, default)]
+        #vis struct #layout {
+            /// Bytes read per snapshot, which must cover every field below
+            pub size: usize,
+            #base_field
+            #(pub #field_idents: isize,)*
+        }
+        impl Default for #layout {
+            fn default() -> Self {
+                Self {
+                    size: #size,
+                    #base_default
+                    #(#field_idents: #offsets,)*
+                }
+            }
+        }
+
+        #(#assertions)*
+
+        impl crate::remote_struct::RemoteStruct for #ident {
+            const NAME: &'static str = #name;
+            const SIZE: usize = #size;
+            const FIELDS: &'static [crate::remote_struct::FieldInfo] = &[#(#field_infos),*];
+
+            type Layout = #layout;
+
+            fn layout(layout: &crate::layout::Layout) -> &Self::Layout {
+                &layout.#root
+            }
+
+            fn size(layout: &Self::Layout) -> usize {
+                layout.size
+            }
+
+            fn offset_of(layout: &Self::Layout, field: &str) -> Option<isize> {
+                match field {
+                    #(stringify!(#field_idents) => Some(#base_offset layout.#field_idents),)*
+                    _ => None,
+                }
+            }
+
+            fn from_view<P: crate::process::ProcessMemory + Copy>(
+                ctx: &crate::remote_struct::ReadContext<P>,
+                view: crate::snapshot::View,
+            ) -> Result<Self, crate::error::Error> {
+                let layout = <Self as crate::remote_struct::RemoteStruct>::layout(ctx.layout);
+                let view = view.named(#name);
+                #errors_init
+
+                #(#decode)*
+                #errors_field
+
+                Ok(Self { #(#all_idents),* })
+            }
+        }
+    })
+}
+
+fn parse_field(field: &syn::Field) -> syn::Result<Field> {
+    let ident = field.ident.clone().unwrap();
+    let mut offset = None;
+    let mut kind = None;
+
+    let mut set_kind = |span, value| {
+        if kind.replace(value).is_some() {
+            Err(syn::Error::new(span, "only one kind of field may be given"))
+        } else {
+            Ok(())
+        }
+    };
+
+    for attr in field.attrs.iter().filter(|x| x.path().is_ident("remote")) {
+        attr.parse_nested_meta(|meta| {
+            let span = meta.path.span();
+            let path = &meta.path;
+
+            if path.is_ident("offset") {
+                offset = Some(meta.value()?.parse::<LitInt>()?);
+            } else if path.is_ident("lookup") {
+                set_kind(span, Kind::Lookup(generic_argument(&field.ty, 1)?))?;
+            } else if path.is_ident("ids") {
+                set_kind(span, Kind::Ids(meta.value()?.parse()?))?;
+            } else if path.is_ident("res_ref") {
+                set_kind(span, Kind::ResRef)?;
+            } else if path.is_ident("text") {
+                set_kind(span, Kind::Text(meta.value()?.parse()?))?;
+            } else if path.is_ident("c_string") {
+                set_kind(span, Kind::CString(meta.value()?.parse()?))?;
+            } else if path.is_ident("nested") {
+                set_kind(span, Kind::Nested)?;
+            } else if path.is_ident("list") {
+                set_kind(span, Kind::List(generic_argument(&field.ty, 0)?))?;
+            } else if path.is_ident("errors") {
+                set_kind(span, Kind::Errors)?;
+            } else if path.is_ident("skip") {
+                set_kind(span, Kind::Skip)?;
+            } else {
+                return Err(meta.error("unknown remote attribute"));
+            }
+
+            Ok(())
+        })?;
+    }
+
+    let kind = kind.unwrap_or(Kind::Value);
+    let needs_offset = !matches!(kind, Kind::Errors | Kind::Skip);
+
+    match (&offset, needs_offset) {
+        (None, true) => Err(syn::Error::new(
+            ident.span(),
+            "missing #[remote(offset = ..)]",
+        )),
+        (Some(x), false) => Err(syn::Error::new(
+            x.span(),
+            "errors and skip fields have no offset",
+        )),
+        _ => Ok(Field {
+            ident,
+            ty: field.ty.clone(),
+            offset,
+            kind,
+        }),
+    }
+}
+
+/// Bytes a field takes up in the structure, for the size assertions
+fn field_size(field: &Field) -> TokenStream2 {
+    let ty = &field.ty;
+
+    match &field.kind {
+        Kind::Value => quote!(size_of::<#ty>()),
+        Kind::Lookup(raw) => quote!(size_of::<#raw>()),
+        Kind::Ids(_) => quote!(1),
+        Kind::ResRef => quote!(8),
+        Kind::Text(size) => quote!(#size),
+        Kind::CString(_) => quote!(size_of::<usize>()),
+        Kind::Nested => quote!(<#ty as crate::remote_struct::RemoteStruct>::SIZE),
+        // The list header is covered by the `CPtrList` layout
+        Kind::List(_) => quote!(0),
+        Kind::Errors | Kind::Skip => quote!(0),
+    }
+}
+
+/// `index`th generic argument of e.g. `Lookup<T, U>` or `Vec<T>`
+fn generic_argument(ty: &Type, index: usize) -> syn::Result<Type> {
+    let error = || syn::Error::new(ty.span(), "expected a type with generic arguments");
+
+    let Type::Path(path) = ty else {
+        return Err(error());
+    };
+    let segment = path.path.segments.last().ok_or_else(error)?;
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return Err(error());
+    };
+
+    arguments
+        .args
+        .iter()
+        .filter_map(|x| match x {
+            GenericArgument::Type(x) => Some(x.clone()),
+            _ => None,
+        })
+        .nth(index)
+        .ok_or_else(error)
+}
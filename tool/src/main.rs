@@ -1,31 +1,262 @@
 use core::{
+    clock::GameTime,
+    describe::describe_effects,
     error::Error,
-    find_game_process, get_static_entity_list,
-    types::{CGameAIBase, CGameSprite, ObjectType},
+    get_static_entity_list,
+    language::Language,
+    list_games,
+    object_spec::ObjectSpec,
+    pointer_path::PointerPath,
+    process::GameProcess,
+    procfs::{GameSelector, Procfs},
+    types::{CGameAIBase, CGameSprite},
+    watcher::{GameProcessWatcher, WatchEvent},
 };
+use std::{
+    process::ExitCode,
+    time::{Duration, SystemTime},
+};
+
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+const USAGE: &str = "Usage: bg-radar-linux [--watch] [--list] [--pid <pid>] [--title <title>] \
+                     [--language <locale>] [--path <path>] [--filter <object>]
+
+  --watch          keep running across game restarts
+  --list           list running games and exit
+  --pid <pid>      attach to the game with this pid
+  --title <title>  attach to a game of this title, e.g. BGEE or BG2EE
+  --language <locale>
+                   decode text as this language, e.g. pl_PL, instead of detecting it
+  --path <path>    print the value at a pointer path and exit,
+                   e.g. 'base+0xCBF780 -> [8] -> [0x3910] -> cstr(64)'
+  --filter <object>
+                   only print creatures matching a script object specifier,
+                   e.g. '[ENEMY.HUMANOID.0.MAGE_ALL]' or '\"Imoen\"'";
+
+#[derive(Debug, Default)]
+struct Args {
+    watch: bool,
+    list: bool,
+    selector: GameSelector,
+    language: Option<Language>,
+    path: Option<PointerPath>,
+    filter: Option<ObjectSpec>,
+}
+impl Args {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Self::default();
+
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or_else(|| format!("{arg} needs a value"));
+
+            match arg.as_str() {
+                "--watch" => parsed.watch = true,
+                "--list" => parsed.list = true,
+                "--pid" => {
+                    let pid = value()?;
+                    let pid = pid.parse().map_err(|_| format!("Invalid pid {pid}"))?;
+                    parsed.selector = GameSelector::Pid(pid);
+                }
+                "--title" => {
+                    let title = value()?.parse().map_err(|e: Error| e.to_string())?;
+                    parsed.selector = GameSelector::Title(title);
+                }
+                "--language" => {
+                    let language = value()?.parse().map_err(|e: Error| e.to_string())?;
+                    parsed.language = Some(language);
+                }
+                "--path" => {
+                    let path = value()?.parse().map_err(|e: Error| e.to_string())?;
+                    parsed.path = Some(path);
+                }
+                "--filter" => {
+                    let filter = value()?.parse().map_err(|e: Error| e.to_string())?;
+                    parsed.filter = Some(filter);
+                }
+                _ => return Err(format!("Unknown argument {arg}")),
+            }
+        }
+
+        Ok(parsed)
+    }
+}
 
-fn main() -> Result<(), Error> {
-    let game_process = find_game_process(true)?;
-    let entities = get_static_entity_list(&game_process)?;
+fn print_sprites(
+    game_process: &GameProcess,
+    language: Option<Language>,
+    filter: Option<&ObjectSpec>,
+) -> Result<(), Error> {
+    let entities = get_static_entity_list(game_process)?;
+    let language = language.unwrap_or(game_process.language);
+    let now = GameTime::read(game_process)?;
+    if now.is_none() {
+        eprintln!("The game clock hasn't been located for this build, so durations aren't shown");
+    }
 
     entities
         .into_iter()
         .filter(|x| x.id != u16::MAX)
         .map(|x| {
-            let base = CGameAIBase::new(&game_process, &x);
+            let base = CGameAIBase::new(
+                game_process,
+                &game_process.layout,
+                game_process.title.ids(),
+                language,
+                &x,
+            );
 
             base.map(|base| (x, base))
         })
         .filter_map(|x| {
             if let Ok((entity, Some(base))) = x
-                && base.object.object_type == ObjectType::Sprite
+                && base.object.is_sprite()
+                && filter.is_none_or(|f| f.matches(&base.object.type_ai))
             {
-                CGameSprite::new(&game_process, &entity, base).unwrap()
+                // The game may free a creature while it's being read, so skip it rather than stop
+                match CGameSprite::new(
+                    game_process,
+                    &game_process.layout,
+                    game_process.title.ids(),
+                    language,
+                    &entity,
+                    base,
+                ) {
+                    Ok(x) => x,
+                    Err(e) => {
+                        eprintln!("Skipping entity {}: {}", entity.id, e.report());
+                        None
+                    }
+                }
             } else {
                 None
             }
         })
-        .for_each(|x| println!("{x:#?}"));
+        .for_each(|x| {
+            println!("{x:#?}");
+
+            let descriptions = describe_effects(&x.equipped_effects, None)
+                .into_iter()
+                .chain(describe_effects(&x.timed_effects, now));
+            for description in descriptions {
+                println!("  {description}");
+            }
+
+            let defences = x.defences().summary();
+            if !defences.is_empty() {
+                println!("  Defences:");
+                for line in defences {
+                    println!("    {line}");
+                }
+            }
+        });
+
+    Ok(())
+}
+
+fn print_attached(game_process: &GameProcess) {
+    eprintln!(
+        "Attached to {} ({}, pid {}) through {}",
+        game_process.title, game_process.language, game_process.pid, game_process.namespace
+    );
+}
+
+fn print_games() -> Result<(), Error> {
+    let now = SystemTime::now();
+
+    for game in list_games()? {
+        let started = game
+            .start_time
+            .and_then(|x| now.duration_since(x).ok())
+            .map(|x| format!("started {}s ago", x.as_secs()))
+            .unwrap_or_else(|| "start time unknown".into());
+
+        println!(
+            "{}\t{}\t{started}\t{}",
+            game.pid,
+            game.title,
+            game.exe.display()
+        );
+    }
 
     Ok(())
 }
+
+/// Keeps running across game restarts, dumping the sprites each time the game is (re)attached
+fn watch(
+    selector: GameSelector,
+    language: Option<Language>,
+    filter: Option<&ObjectSpec>,
+) -> Result<(), Error> {
+    let mut watcher = GameProcessWatcher::new().with_selector(selector);
+    let mut reported = None;
+
+    loop {
+        let Some(event) = watcher.poll()? else {
+            // Reported once rather than on every retry
+            let error = watcher.attach_error().map(|e| e.report());
+            if let Some(error) = &error
+                && reported.as_ref() != Some(error)
+            {
+                eprintln!("Couldn't attach, retrying: {error}");
+            }
+            reported = error;
+
+            std::thread::sleep(WATCH_INTERVAL);
+            continue;
+        };
+        println!("{event:?}");
+
+        if let WatchEvent::Detached { .. } = event {
+            continue;
+        }
+
+        if let Some(process) = watcher.process() {
+            print_attached(process);
+
+            match print_sprites(process, language, filter) {
+                Err(e) if matches!(e.root(), Error::GameProcessClosed) => continue,
+                x => x?,
+            }
+        }
+    }
+}
+
+fn run(args: Args) -> Result<(), Error> {
+    if args.list {
+        return print_games();
+    }
+
+    if args.watch {
+        return watch(args.selector, args.language, args.filter.as_ref());
+    }
+
+    let game_process = Procfs::default().find_game_process_by(&args.selector, true)?;
+    print_attached(&game_process);
+
+    if let Some(path) = args.path {
+        let value = path.eval(&game_process, game_process.base_address.get())?;
+        println!("{path} = {value}");
+        return Ok(());
+    }
+
+    print_sprites(&game_process, args.language, args.filter.as_ref())
+}
+
+fn main() -> ExitCode {
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(x) => x,
+        Err(e) => {
+            eprintln!("{e}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e.report());
+            ExitCode::FAILURE
+        }
+    }
+}